@@ -0,0 +1,534 @@
+//! ANSI terminal escape sequences
+
+use crate::space::linear_to_oklab;
+use crate::{Color, Style, Text};
+use std::sync::OnceLock;
+
+/// How many colors the terminal supports
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ColorDepth {
+    /// 24-bit `38;2;r;g;b` colors
+    #[default]
+    TrueColor,
+    /// The xterm 256-color palette
+    Ansi256,
+    /// The 16 basic and bright colors
+    Ansi16,
+}
+
+/// How obfuscated (`§k`) text is shown, since terminals cannot animate it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ObfuscatedFallback {
+    /// Show the text unchanged
+    #[default]
+    Plain,
+    /// Show the text blinking (SGR 5)
+    Blink,
+    /// Hide the text (SGR 8)
+    Conceal,
+    /// Replace every character with the given one
+    Replace(char),
+}
+
+/// The 16 basic colors as most terminals show them (the VGA palette)
+pub(crate) const VGA_PALETTE: [Color; 16] = [
+    Color::MC_BLACK,
+    Color::MC_DARK_RED,
+    Color::MC_DARK_GREEN,
+    Color {
+        r: 170,
+        g: 85,
+        b: 0,
+        a: 1.0,
+    },
+    Color::MC_DARK_BLUE,
+    Color::MC_DARK_PURPLE,
+    Color::MC_DARK_AQUA,
+    Color::MC_GRAY,
+    Color::MC_DARK_GRAY,
+    Color::MC_RED,
+    Color::MC_GREEN,
+    Color::MC_YELLOW,
+    Color::MC_BLUE,
+    Color::MC_LIGHT_PURPLE,
+    Color::MC_AQUA,
+    Color::MC_WHITE,
+];
+
+/// Channel levels of the xterm 6x6x6 color cube
+const CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+/// The xterm palette color at an index from 16 to 255
+fn xterm_color(index: u8) -> Color {
+    match index {
+        16..=231 => {
+            let i = index as usize - 16;
+            Color::from_rgb(
+                CUBE_LEVELS[i / 36],
+                CUBE_LEVELS[i / 6 % 6],
+                CUBE_LEVELS[i % 6],
+            )
+        }
+        _ => {
+            let level = 8 + 10 * index.saturating_sub(232);
+            Color::from_rgb(level, level, level)
+        }
+    }
+}
+
+/// Oklab coordinates of xterm colors 16 to 255, whose appearance does not
+/// depend on the terminal theme
+fn xterm_oklab() -> &'static [[f32; 3]; 240] {
+    static PALETTE: OnceLock<[[f32; 3]; 240]> = OnceLock::new();
+    PALETTE.get_or_init(|| {
+        std::array::from_fn(|i| linear_to_oklab(xterm_color(i as u8 + 16).to_linear_rgb()))
+    })
+}
+
+/// Oklab coordinates of the VGA palette
+fn vga_oklab() -> &'static [[f32; 3]; 16] {
+    static PALETTE: OnceLock<[[f32; 3]; 16]> = OnceLock::new();
+    PALETTE.get_or_init(|| VGA_PALETTE.map(|color| linear_to_oklab(color.to_linear_rgb())))
+}
+
+/// The index of the palette entry closest to `color` in Oklab
+fn nearest_oklab(color: &Color, palette: &[[f32; 3]]) -> usize {
+    let lab = linear_to_oklab(color.to_linear_rgb());
+    let distance = |other: &[f32; 3]| (0..3).map(|i| (lab[i] - other[i]).powi(2)).sum::<f32>();
+    (0..palette.len())
+        .min_by(|&a, &b| {
+            distance(&palette[a])
+                .partial_cmp(&distance(&palette[b]))
+                .unwrap()
+        })
+        .unwrap()
+}
+
+/// SGR parameters setting the foreground or background color
+fn color_params(color: &Color, depth: ColorDepth, background: bool) -> String {
+    let layer = if background { 4 } else { 3 };
+    match depth {
+        ColorDepth::TrueColor => format!("{}8;2;{};{};{}", layer, color.r, color.g, color.b),
+        ColorDepth::Ansi256 => format!("{}8;5;{}", layer, color.to_ansi256()),
+        ColorDepth::Ansi16 => match color.to_ansi16() {
+            i @ 0..=7 => (layer * 10 + i).to_string(),
+            i => (layer * 10 + 60 + i - 8).to_string(),
+        },
+    }
+}
+
+impl Color {
+    /// The nearest xterm 256-color index, from the 6x6x6 cube or the gray
+    /// ramp (16 to 255), by Oklab distance
+    pub fn to_ansi256(&self) -> u8 {
+        nearest_oklab(self, xterm_oklab()) as u8 + 16
+    }
+
+    /// The nearest of the 16 basic colors (0 to 15), as the VGA palette
+    /// shows them, by Oklab distance
+    pub fn to_ansi16(&self) -> u8 {
+        nearest_oklab(self, vga_oklab()) as u8
+    }
+
+    /// The SGR escape sequence that sets this as the foreground color
+    pub fn to_ansi_foreground(&self, depth: ColorDepth) -> String {
+        format!("\x1b[{}m", color_params(self, depth, false))
+    }
+
+    /// The SGR escape sequence that sets this as the background color
+    pub fn to_ansi_background(&self, depth: ColorDepth) -> String {
+        format!("\x1b[{}m", color_params(self, depth, true))
+    }
+}
+
+/// SGR parameters for a style, without a reset
+fn style_params(style: &Style, depth: ColorDepth, obfuscated: ObfuscatedFallback) -> Vec<String> {
+    let mut params = Vec::new();
+    let flags = [
+        (style.bold, "1"),
+        (style.italic, "3"),
+        (style.underlined, "4"),
+        (style.strikethrough, "9"),
+        (
+            style.obfuscated && obfuscated == ObfuscatedFallback::Blink,
+            "5",
+        ),
+        (
+            style.obfuscated && obfuscated == ObfuscatedFallback::Conceal,
+            "8",
+        ),
+    ];
+    for (enabled, param) in flags {
+        if enabled {
+            params.push(param.to_string());
+        }
+    }
+    if let Some(color) = &style.color {
+        params.push(color_params(color, depth, false));
+    }
+    if let Some(background) = &style.background {
+        params.push(color_params(background, depth, true));
+    }
+    params
+}
+
+impl Text {
+    /// Render to ANSI escape sequences for a terminal
+    ///
+    /// Bold, italic, underline and strikethrough map to their SGR codes, and
+    /// obfuscated text is shown as `obfuscated` specifies. The output ends
+    /// with a reset if any style was applied.
+    pub fn to_ansi(&self, depth: ColorDepth, obfuscated: ObfuscatedFallback) -> String {
+        let mut result = String::new();
+        let mut styled = false;
+        let mut current = Style::default();
+
+        for span in &self.spans {
+            if span.style != current {
+                let mut params = style_params(&span.style, depth, obfuscated);
+                if styled {
+                    params.insert(0, "0".to_string());
+                }
+                if !params.is_empty() {
+                    result.push_str(&format!("\x1b[{}m", params.join(";")));
+                }
+                styled = params.len() > usize::from(styled);
+                current = span.style.clone();
+            }
+
+            match obfuscated {
+                ObfuscatedFallback::Replace(c) if span.style.obfuscated => {
+                    result.extend(
+                        span.text
+                            .chars()
+                            .map(|ch| if ch.is_whitespace() { ch } else { c }),
+                    );
+                }
+                _ => result.push_str(&span.text),
+            }
+        }
+
+        if styled {
+            result.push_str("\x1b[0m");
+        }
+        result
+    }
+}
+
+/// The colors a terminal shows for the 16 basic SGR colors
+#[derive(Debug, Clone, PartialEq)]
+pub struct AnsiPalette {
+    pub colors: [Color; 16], // Black to white, then the bright variants
+}
+
+impl Default for AnsiPalette {
+    fn default() -> Self {
+        AnsiPalette::vga()
+    }
+}
+
+impl AnsiPalette {
+    /// A palette with the given colors
+    pub fn new(colors: [Color; 16]) -> Self {
+        AnsiPalette { colors }
+    }
+
+    /// The VGA palette, which matches the Minecraft legacy colors except for
+    /// brown (170, 85, 0) at index 3 in place of gold
+    pub fn vga() -> Self {
+        AnsiPalette::new(VGA_PALETTE)
+    }
+
+    /// xterm's default palette
+    pub fn xterm() -> Self {
+        AnsiPalette::new(
+            [
+                0x000000, 0xCD0000, 0x00CD00, 0xCDCD00, 0x0000EE, 0xCD00CD, 0x00CDCD, 0xE5E5E5,
+                0x7F7F7F, 0xFF0000, 0x00FF00, 0xFFFF00, 0x5C5CFF, 0xFF00FF, 0x00FFFF, 0xFFFFFF,
+            ]
+            .map(Color::from_packed),
+        )
+    }
+
+    /// The color of a 256-color index, using this palette below 16
+    fn color(&self, index: u8) -> Color {
+        match index {
+            0..=15 => self.colors[index as usize].clone(),
+            _ => xterm_color(index),
+        }
+    }
+}
+
+/// Parse the arguments of an extended color (`38`/`48`): `5;n` or `2;r;g;b`
+///
+/// Returns the color, if valid, and how many arguments it used. With colons
+/// the RGB form may have an extra color space ID before the channels.
+fn extended_color(
+    args: &[Option<u16>],
+    colons: bool,
+    palette: &AnsiPalette,
+) -> (Option<Color>, usize) {
+    let channel = |arg: Option<u16>| arg.and_then(|c| u8::try_from(c).ok());
+    match args.first() {
+        Some(Some(5)) => {
+            let color = args.get(1).copied().and_then(channel);
+            (color.map(|index| palette.color(index)), 2)
+        }
+        Some(Some(2)) => {
+            let skip = usize::from(colons && args.len() > 4);
+            let rgb: Option<Vec<u8>> = (0..3)
+                .map(|i| args.get(1 + skip + i).copied().and_then(channel))
+                .collect();
+            (
+                rgb.map(|rgb| Color::from_rgb(rgb[0], rgb[1], rgb[2])),
+                4 + skip,
+            )
+        }
+        _ => (None, 1),
+    }
+}
+
+/// Apply the parameters of an SGR sequence (`ESC [ ... m`) to a style
+fn apply_sgr(params: &str, style: &mut Style, palette: &AnsiPalette) {
+    // An empty parameter means 0; parameters that fail to parse are `None`
+    let groups: Vec<Vec<Option<u16>>> = params
+        .split(';')
+        .map(|group| {
+            group
+                .split(':')
+                .map(|p| {
+                    if p.is_empty() {
+                        Some(0)
+                    } else {
+                        p.parse().ok()
+                    }
+                })
+                .collect()
+        })
+        .collect();
+
+    let mut i = 0;
+    while i < groups.len() {
+        let group = &groups[i];
+        i += 1;
+        let Some(code) = group[0] else {
+            continue;
+        };
+        match code {
+            0 => *style = Style::default(),
+            1 => style.bold = true,
+            3 => style.italic = true,
+            4 => style.underlined = group.get(1) != Some(&Some(0)),
+            9 => style.strikethrough = true,
+            22 => style.bold = false,
+            23 => style.italic = false,
+            24 => style.underlined = false,
+            29 => style.strikethrough = false,
+            30..=37 => style.color = Some(palette.color(code as u8 - 30)),
+            39 => style.color = None,
+            40..=47 => style.background = Some(palette.color(code as u8 - 40)),
+            49 => style.background = None,
+            90..=97 => style.color = Some(palette.color(code as u8 - 90 + 8)),
+            100..=107 => style.background = Some(palette.color(code as u8 - 100 + 8)),
+            38 | 48 => {
+                let color = if group.len() > 1 {
+                    extended_color(&group[1..], true, palette).0
+                } else {
+                    let args: Vec<Option<u16>> = groups[i..].iter().map(|g| g[0]).collect();
+                    let (color, used) = extended_color(&args, false, palette);
+                    i += used.min(args.len());
+                    color
+                };
+                if let Some(color) = color {
+                    if code == 38 {
+                        style.color = Some(color);
+                    } else {
+                        style.background = Some(color);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+impl Text {
+    /// Parse text with ANSI SGR sequences, using the VGA palette for the 16
+    /// basic colors
+    pub fn from_ansi(input: &str) -> Text {
+        Text::from_ansi_in(input, &AnsiPalette::default())
+    }
+
+    /// Parse text with ANSI SGR sequences, resolving the 16 basic colors
+    /// through `palette`
+    ///
+    /// Supports `30`–`37`, `90`–`97`, their backgrounds, `38;5;n`,
+    /// `38;2;r;g;b` and the colon forms, `39`/`49` and the bold, italic,
+    /// underline and strikethrough codes. Other escape sequences are removed.
+    pub fn from_ansi_in(input: &str, palette: &AnsiPalette) -> Text {
+        let mut text = Text::new();
+        let mut style = Style::default();
+        let mut rest = input;
+
+        while let Some(start) = rest.find('\x1b') {
+            text.push(&rest[..start], &style);
+            rest = &rest[start + 1..];
+            match rest.chars().next() {
+                // CSI: parameter bytes up to a final byte from `@` to `~`
+                Some('[') => match rest[1..].find(|c: char| ('@'..='~').contains(&c)) {
+                    Some(end) => {
+                        // Private sequences such as `ESC [ > 4 ; 2 m` are not SGR
+                        let params = &rest[1..1 + end];
+                        if rest[1 + end..].starts_with('m')
+                            && !params.starts_with(['<', '=', '>', '?'])
+                        {
+                            apply_sgr(params, &mut style, palette);
+                        }
+                        rest = &rest[2 + end..];
+                    }
+                    None => rest = "",
+                },
+                // OSC: ends with BEL or `ESC \`
+                Some(']') => {
+                    let bel = rest.find('\x07').map(|i| i + 1);
+                    let st = rest.find("\x1b\\").map(|i| i + 2);
+                    let end = bel.into_iter().chain(st).min().unwrap_or(rest.len());
+                    rest = &rest[end..];
+                }
+                Some(c) => rest = &rest[c.len_utf8()..],
+                None => {}
+            }
+        }
+
+        text.push(rest, &style);
+        text
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_color_depths() {
+        let color = Color::from_rgb(255, 87, 51);
+        assert_eq!(
+            color.to_ansi_foreground(ColorDepth::TrueColor),
+            "\x1b[38;2;255;87;51m"
+        );
+        // #FF5F00 is perceptually closer than the nearer-in-RGB #FF5F5F
+        assert_eq!(color.to_ansi256(), 202);
+        assert_eq!(
+            color.to_ansi_foreground(ColorDepth::Ansi256),
+            "\x1b[38;5;202m"
+        );
+        assert_eq!(color.to_ansi_foreground(ColorDepth::Ansi16), "\x1b[91m");
+        assert_eq!(color.to_ansi_background(ColorDepth::Ansi16), "\x1b[101m");
+        assert_eq!(
+            color.to_ansi_background(ColorDepth::TrueColor),
+            "\x1b[48;2;255;87;51m"
+        );
+
+        let gray = Color::from_rgb(128, 128, 128);
+        assert_eq!(gray.to_ansi256(), 244);
+        assert_eq!(
+            gray.to_ansi_background(ColorDepth::Ansi256),
+            "\x1b[48;5;244m"
+        );
+        assert_eq!(Color::MC_DARK_BLUE.to_ansi16(), 4);
+        assert_eq!(
+            Color::MC_DARK_BLUE.to_ansi_foreground(ColorDepth::Ansi16),
+            "\x1b[34m"
+        );
+    }
+
+    #[test]
+    fn test_xterm_palette() {
+        assert_eq!(xterm_color(16), Color::BLACK);
+        assert_eq!(xterm_color(196), Color::from_rgb(255, 0, 0));
+        assert_eq!(xterm_color(255), Color::from_rgb(238, 238, 238));
+        // Exact palette colors map to themselves
+        for index in [21, 46, 130, 201, 232, 250] {
+            assert_eq!(xterm_color(index).to_ansi256(), index);
+        }
+        // Dark saturated blue stays in the cube rather than going gray
+        assert_eq!(Color::from_rgb(20, 20, 90).to_ansi256(), 17);
+    }
+
+    #[test]
+    fn test_to_ansi() {
+        let text = Text::from_legacy("§c§lHello §r§nworld§r!");
+        assert_eq!(
+            text.to_ansi(ColorDepth::Ansi16, ObfuscatedFallback::Plain),
+            "\x1b[1;91mHello \x1b[0;4mworld\x1b[0m!"
+        );
+        assert_eq!(
+            Text::from_legacy("plain").to_ansi(ColorDepth::TrueColor, ObfuscatedFallback::Plain),
+            "plain"
+        );
+    }
+
+    #[test]
+    fn test_obfuscated_fallback() {
+        let text = Text::from_legacy("&kab c");
+        assert_eq!(
+            text.to_ansi(ColorDepth::TrueColor, ObfuscatedFallback::Replace('?')),
+            "?? ?"
+        );
+        assert_eq!(
+            text.to_ansi(ColorDepth::TrueColor, ObfuscatedFallback::Conceal),
+            "\x1b[8mab c\x1b[0m"
+        );
+    }
+
+    #[test]
+    fn test_from_ansi() {
+        let text = Text::from_ansi("\x1b[1;91mError:\x1b[22m \x1b[38;5;244mgray\x1b[0m done");
+        assert_eq!(text.plain(), "Error: gray done");
+        assert_eq!(text.spans[0].style.color, Some(Color::MC_RED));
+        assert!(text.spans[0].style.bold);
+        assert!(!text.spans[1].style.bold);
+        assert_eq!(
+            text.spans[2].style.color,
+            Some(Color::from_rgb(128, 128, 128))
+        );
+        assert_eq!(text.spans[3].style, Style::default());
+
+        let text = Text::from_ansi("\x1b[38:2::255:87:51;48:2:1:2:3mA\x1b[39;4mB\x1b[49;24mC");
+        assert_eq!(
+            text.spans[0].style.color,
+            Some(Color::from_rgb(255, 87, 51))
+        );
+        assert_eq!(
+            text.spans[0].style.background,
+            Some(Color::from_rgb(1, 2, 3))
+        );
+        assert_eq!(text.spans[1].style.color, None);
+        assert!(text.spans[1].style.underlined);
+        assert_eq!(text.spans[2].style, Style::default());
+
+        // Private and out-of-range parameters do not reset the style
+        let text = Text::from_ansi("\x1b[1;31mA\x1b[>4;2mB\x1b[70000mC\x1b[;mD");
+        assert_eq!(text.spans[0].text, "ABC");
+        assert!(text.spans[0].style.bold);
+        assert_eq!(text.spans[1].text, "D");
+        assert_eq!(text.spans[1].style, Style::default());
+    }
+
+    #[test]
+    fn test_from_ansi_palette_and_other_sequences() {
+        let input = "\x1b]0;title\x07\x1b[2K\x1b[31;104mred\x1b[m";
+        let text = Text::from_ansi_in(input, &AnsiPalette::xterm());
+        assert_eq!(text.plain(), "red");
+        assert_eq!(text.spans[0].style.color, Some(Color::from_rgb(205, 0, 0)));
+        assert_eq!(
+            text.spans[0].style.background,
+            Some(Color::from_rgb(92, 92, 255))
+        );
+
+        // Rendering and parsing again keeps the style
+        let text = Text::from_legacy("§c§lHello §r§nworld");
+        let ansi = text.to_ansi(ColorDepth::TrueColor, ObfuscatedFallback::Plain);
+        assert_eq!(Text::from_ansi(&ansi), text);
+    }
+}
@@ -0,0 +1,397 @@
+//! Alpha compositing with the Porter-Duff operators and W3C blend modes
+//!
+//! Compositing happens on gamma-encoded sRGB channels, as in CSS and the W3C
+//! Compositing and Blending specification.
+
+use crate::Color;
+
+/// Color with alpha-premultiplied channels
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Premultiplied {
+    pub r: f32, // 0.0 to a
+    pub g: f32, // 0.0 to a
+    pub b: f32, // 0.0 to a
+    pub a: f32, // 0.0 to 1.0
+}
+
+/// Porter-Duff compositing operators
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompositeOp {
+    /// Neither source nor backdrop is shown
+    Clear,
+    /// Only the source is shown
+    Src,
+    /// Only the backdrop is shown
+    Dst,
+    /// Source placed over the backdrop
+    SrcOver,
+    /// Backdrop placed over the source
+    DstOver,
+    /// Source where the backdrop is
+    SrcIn,
+    /// Backdrop where the source is
+    DstIn,
+    /// Source where the backdrop is not
+    SrcOut,
+    /// Backdrop where the source is not
+    DstOut,
+    /// Source over the backdrop, only where the backdrop is
+    SrcAtop,
+    /// Backdrop over the source, only where the source is
+    DstAtop,
+    /// Source and backdrop where they do not overlap
+    Xor,
+    /// Sum of source and backdrop, clamped (CSS `plus-lighter`)
+    Plus,
+}
+
+/// W3C Compositing and Blending `mix-blend-mode` operations
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BlendMode {
+    Normal,
+    Multiply,
+    Screen,
+    Overlay,
+    Darken,
+    Lighten,
+    ColorDodge,
+    ColorBurn,
+    HardLight,
+    SoftLight,
+    Difference,
+    Exclusion,
+    Hue,
+    Saturation,
+    Color,
+    Luminosity,
+}
+
+impl CompositeOp {
+    /// Porter-Duff coefficients (Fa, Fb) for source and backdrop alpha
+    fn factors(self, alpha_s: f32, alpha_b: f32) -> (f32, f32) {
+        match self {
+            CompositeOp::Clear => (0.0, 0.0),
+            CompositeOp::Src => (1.0, 0.0),
+            CompositeOp::Dst => (0.0, 1.0),
+            CompositeOp::SrcOver => (1.0, 1.0 - alpha_s),
+            CompositeOp::DstOver => (1.0 - alpha_b, 1.0),
+            CompositeOp::SrcIn => (alpha_b, 0.0),
+            CompositeOp::DstIn => (0.0, alpha_s),
+            CompositeOp::SrcOut => (1.0 - alpha_b, 0.0),
+            CompositeOp::DstOut => (0.0, 1.0 - alpha_s),
+            CompositeOp::SrcAtop => (alpha_b, 1.0 - alpha_s),
+            CompositeOp::DstAtop => (1.0 - alpha_b, alpha_s),
+            CompositeOp::Xor => (1.0 - alpha_b, 1.0 - alpha_s),
+            CompositeOp::Plus => (1.0, 1.0),
+        }
+    }
+}
+
+impl BlendMode {
+    /// Blend backdrop and source channels (0.0 to 1.0) per the W3C formulas
+    fn apply(self, cb: [f32; 3], cs: [f32; 3]) -> [f32; 3] {
+        let separable =
+            |f: fn(f32, f32) -> f32| [f(cb[0], cs[0]), f(cb[1], cs[1]), f(cb[2], cs[2])];
+
+        match self {
+            BlendMode::Normal => cs,
+            BlendMode::Multiply => separable(multiply),
+            BlendMode::Screen => separable(screen),
+            BlendMode::Overlay => separable(|b, s| hard_light(s, b)),
+            BlendMode::Darken => separable(f32::min),
+            BlendMode::Lighten => separable(f32::max),
+            BlendMode::ColorDodge => separable(color_dodge),
+            BlendMode::ColorBurn => separable(color_burn),
+            BlendMode::HardLight => separable(hard_light),
+            BlendMode::SoftLight => separable(soft_light),
+            BlendMode::Difference => separable(|b, s| (b - s).abs()),
+            BlendMode::Exclusion => separable(|b, s| b + s - 2.0 * b * s),
+            BlendMode::Hue => set_lum(set_sat(cs, sat(cb)), lum(cb)),
+            BlendMode::Saturation => set_lum(set_sat(cb, sat(cs)), lum(cb)),
+            BlendMode::Color => set_lum(cs, lum(cb)),
+            BlendMode::Luminosity => set_lum(cb, lum(cs)),
+        }
+    }
+}
+
+fn multiply(cb: f32, cs: f32) -> f32 {
+    cb * cs
+}
+
+fn screen(cb: f32, cs: f32) -> f32 {
+    cb + cs - cb * cs
+}
+
+fn hard_light(cb: f32, cs: f32) -> f32 {
+    if cs <= 0.5 {
+        multiply(cb, 2.0 * cs)
+    } else {
+        screen(cb, 2.0 * cs - 1.0)
+    }
+}
+
+fn color_dodge(cb: f32, cs: f32) -> f32 {
+    if cb == 0.0 {
+        0.0
+    } else if cs >= 1.0 {
+        1.0
+    } else {
+        (cb / (1.0 - cs)).min(1.0)
+    }
+}
+
+fn color_burn(cb: f32, cs: f32) -> f32 {
+    if cb >= 1.0 {
+        1.0
+    } else if cs == 0.0 {
+        0.0
+    } else {
+        1.0 - ((1.0 - cb) / cs).min(1.0)
+    }
+}
+
+fn soft_light(cb: f32, cs: f32) -> f32 {
+    if cs <= 0.5 {
+        cb - (1.0 - 2.0 * cs) * cb * (1.0 - cb)
+    } else {
+        let d = if cb <= 0.25 {
+            ((16.0 * cb - 12.0) * cb + 4.0) * cb
+        } else {
+            cb.sqrt()
+        };
+        cb + (2.0 * cs - 1.0) * (d - cb)
+    }
+}
+
+fn lum(c: [f32; 3]) -> f32 {
+    0.3 * c[0] + 0.59 * c[1] + 0.11 * c[2]
+}
+
+fn clip_color(c: [f32; 3]) -> [f32; 3] {
+    let l = lum(c);
+    let n = c[0].min(c[1]).min(c[2]);
+    let x = c[0].max(c[1]).max(c[2]);
+
+    c.map(|v| {
+        let mut v = v;
+        if n < 0.0 {
+            v = l + (v - l) * l / (l - n);
+        }
+        if x > 1.0 {
+            v = l + (v - l) * (1.0 - l) / (x - l);
+        }
+        v
+    })
+}
+
+fn set_lum(c: [f32; 3], l: f32) -> [f32; 3] {
+    let d = l - lum(c);
+    clip_color(c.map(|v| v + d))
+}
+
+fn sat(c: [f32; 3]) -> f32 {
+    c[0].max(c[1]).max(c[2]) - c[0].min(c[1]).min(c[2])
+}
+
+fn set_sat(c: [f32; 3], s: f32) -> [f32; 3] {
+    let mut order = [0, 1, 2];
+    order.sort_by(|&i, &j| c[i].total_cmp(&c[j]));
+    let [min, mid, max] = order;
+
+    let mut result = [0.0; 3];
+    if c[max] > c[min] {
+        result[mid] = (c[mid] - c[min]) * s / (c[max] - c[min]);
+        result[max] = s;
+    }
+    result
+}
+
+impl Premultiplied {
+    /// Convert back to a straight-alpha Color (fully transparent becomes transparent black)
+    pub fn unpremultiply(&self) -> Color {
+        if self.a <= 0.0 {
+            return Color::from_rgba(0, 0, 0, 0.0);
+        }
+
+        let channel = |c: f32| ((c / self.a).clamp(0.0, 1.0) * 255.0).round() as u8;
+        Color::from_rgba(channel(self.r), channel(self.g), channel(self.b), self.a)
+    }
+
+    /// Composite this (source) color onto a backdrop with a Porter-Duff operator
+    pub fn composite(&self, backdrop: &Premultiplied, op: CompositeOp) -> Premultiplied {
+        let (fa, fb) = op.factors(self.a, backdrop.a);
+        let mix = |s: f32, b: f32| (fa * s + fb * b).clamp(0.0, 1.0);
+
+        Premultiplied {
+            r: mix(self.r, backdrop.r),
+            g: mix(self.g, backdrop.g),
+            b: mix(self.b, backdrop.b),
+            a: mix(self.a, backdrop.a),
+        }
+    }
+}
+
+impl Color {
+    /// Convert to premultiplied-alpha channels
+    pub fn premultiply(&self) -> Premultiplied {
+        Premultiplied {
+            r: self.r as f32 / 255.0 * self.a,
+            g: self.g as f32 / 255.0 * self.a,
+            b: self.b as f32 / 255.0 * self.a,
+            a: self.a,
+        }
+    }
+
+    /// Composite this (source) color onto a backdrop with a Porter-Duff operator
+    pub fn composite(&self, backdrop: &Color, op: CompositeOp) -> Color {
+        self.premultiply()
+            .composite(&backdrop.premultiply(), op)
+            .unpremultiply()
+    }
+
+    /// Place this color over a backdrop (source-over compositing)
+    pub fn over(&self, backdrop: &Color) -> Color {
+        self.composite(backdrop, CompositeOp::SrcOver)
+    }
+
+    /// Blend this (source) color onto a backdrop like CSS `mix-blend-mode`
+    ///
+    /// The blended color is weighted by the backdrop alpha and then
+    /// composited with source-over.
+    pub fn mix_blend(&self, backdrop: &Color, mode: BlendMode) -> Color {
+        let channels = |c: &Color| [c.r, c.g, c.b].map(|v| v as f32 / 255.0);
+        let cs = channels(self);
+        let cb = channels(backdrop);
+        let blended = mode.apply(cb, cs);
+
+        let (alpha_s, alpha_b) = (self.a, backdrop.a);
+        let source = [0, 1, 2].map(|i| ((1.0 - alpha_b) * cs[i] + alpha_b * blended[i]) * alpha_s);
+        let source = Premultiplied {
+            r: source[0],
+            g: source[1],
+            b: source[2],
+            a: alpha_s,
+        };
+
+        source
+            .composite(&backdrop.premultiply(), CompositeOp::SrcOver)
+            .unpremultiply()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_premultiply_round_trip() {
+        let color = Color::from_rgba(200, 100, 50, 0.5);
+        let premultiplied = color.premultiply();
+        assert!((premultiplied.r - 200.0 / 255.0 * 0.5).abs() < 1e-6);
+        assert_eq!(premultiplied.unpremultiply(), color);
+
+        let transparent = Color::from_rgba(255, 0, 0, 0.0).premultiply();
+        assert_eq!(transparent.unpremultiply(), Color::from_rgba(0, 0, 0, 0.0));
+    }
+
+    #[test]
+    fn test_over() {
+        assert_eq!(Color::RED.over(&Color::BLUE), Color::RED);
+
+        let half_red = Color::from_rgba(255, 0, 0, 0.5);
+        let result = half_red.over(&Color::BLUE);
+        assert_eq!(result, Color::from_rgba(128, 0, 128, 1.0));
+
+        let half_blue = Color::from_rgba(0, 0, 255, 0.5);
+        let result = half_red.over(&half_blue);
+        assert!((result.a - 0.75).abs() < 1e-6);
+        assert_eq!((result.r, result.g, result.b), (170, 0, 85));
+    }
+
+    #[test]
+    fn test_porter_duff_operators() {
+        let source = Color::from_rgba(255, 0, 0, 0.5);
+        let backdrop = Color::from_rgba(0, 0, 255, 0.5);
+
+        assert_eq!(source.composite(&backdrop, CompositeOp::Src), source);
+        assert_eq!(source.composite(&backdrop, CompositeOp::Dst), backdrop);
+        assert_eq!(
+            source.composite(&backdrop, CompositeOp::Clear),
+            Color::from_rgba(0, 0, 0, 0.0)
+        );
+        assert_eq!(
+            source.composite(&backdrop, CompositeOp::SrcIn),
+            Color::from_rgba(255, 0, 0, 0.25)
+        );
+        assert_eq!(
+            source.composite(&backdrop, CompositeOp::SrcOut),
+            Color::from_rgba(255, 0, 0, 0.25)
+        );
+        assert_eq!(
+            source.composite(&backdrop, CompositeOp::SrcAtop),
+            Color::from_rgba(128, 0, 128, 0.5)
+        );
+        assert_eq!(
+            source.composite(&backdrop, CompositeOp::Xor),
+            Color::from_rgba(128, 0, 128, 0.5)
+        );
+
+        let opaque = Color::RED.composite(&Color::BLUE, CompositeOp::Xor);
+        assert_eq!(opaque.a, 0.0);
+
+        let plus = Color::from_rgb(200, 100, 0)
+            .composite(&Color::from_rgb(100, 100, 0), CompositeOp::Plus);
+        assert_eq!(plus, Color::from_rgb(255, 200, 0));
+    }
+
+    #[test]
+    fn test_separable_blend_modes() {
+        let source = Color::from_rgb(51, 153, 204);
+        let backdrop = Color::from_rgb(204, 102, 51);
+        let blend = |mode| source.mix_blend(&backdrop, mode);
+
+        assert_eq!(blend(BlendMode::Normal), source);
+        assert_eq!(blend(BlendMode::Multiply), Color::from_rgb(41, 61, 41));
+        assert_eq!(blend(BlendMode::Screen), Color::from_rgb(214, 194, 214));
+        assert_eq!(blend(BlendMode::Overlay), Color::from_rgb(173, 122, 82));
+        assert_eq!(blend(BlendMode::Darken), Color::from_rgb(51, 102, 51));
+        assert_eq!(blend(BlendMode::Lighten), Color::from_rgb(204, 153, 204));
+        assert_eq!(blend(BlendMode::ColorDodge), Color::WHITE);
+        assert_eq!(blend(BlendMode::ColorBurn), Color::from_rgb(0, 0, 0));
+        assert_eq!(blend(BlendMode::HardLight), Color::from_rgb(82, 133, 173));
+        assert_eq!(blend(BlendMode::Difference), Color::from_rgb(153, 51, 153));
+        assert_eq!(blend(BlendMode::Exclusion), Color::from_rgb(173, 133, 173));
+    }
+
+    #[test]
+    fn test_non_separable_blend_modes() {
+        let gray = Color::from_rgb(128, 128, 128);
+        assert_eq!(
+            Color::RED.mix_blend(&gray, BlendMode::Luminosity),
+            Color::from_rgb(77, 77, 77)
+        );
+        // A gray source removes the backdrop's saturation but keeps its luminosity
+        assert_eq!(
+            gray.mix_blend(&Color::RED, BlendMode::Saturation),
+            Color::from_rgb(77, 77, 77)
+        );
+
+        let colored = Color::RED.mix_blend(&gray, BlendMode::Color);
+        assert!(colored.r > colored.g && colored.g == colored.b);
+
+        let hue = Color::BLUE.mix_blend(&Color::from_rgb(200, 60, 60), BlendMode::Hue);
+        assert!(hue.b > hue.r && hue.b > hue.g);
+    }
+
+    #[test]
+    fn test_blend_with_alpha() {
+        let source = Color::from_rgba(51, 153, 204, 0.5);
+        let backdrop = Color::from_rgb(204, 102, 51);
+        let result = source.mix_blend(&backdrop, BlendMode::Multiply);
+        assert_eq!(result, Color::from_rgb(122, 82, 46));
+
+        // Over a transparent backdrop the blend mode has no effect
+        let clear = Color::from_rgba(0, 0, 0, 0.0);
+        assert_eq!(source.mix_blend(&clear, BlendMode::Difference), source);
+    }
+}
@@ -0,0 +1,429 @@
+//! CSS `linear-gradient()`, `radial-gradient()` and `conic-gradient()`
+
+use super::{parse_angle, parse_color, split_tokens, split_top_level};
+use crate::{ColorError, ColorSpace, ColorStop, Gradient, HueInterpolation};
+use std::fmt;
+
+/// The CSS gradient function
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GradientKind {
+    Linear,
+    Radial,
+    Conic,
+}
+
+/// A CSS gradient: its function, geometry and color stops
+#[derive(Debug, Clone, PartialEq)]
+pub struct CssGradient {
+    pub kind: GradientKind,
+    pub repeating: bool,
+    pub prelude: String, // Geometry such as "to right" or "circle at top", may be empty
+    pub gradient: Gradient,
+}
+
+impl GradientKind {
+    fn function_name(self) -> &'static str {
+        match self {
+            GradientKind::Linear => "linear-gradient",
+            GradientKind::Radial => "radial-gradient",
+            GradientKind::Conic => "conic-gradient",
+        }
+    }
+}
+
+fn space_name(space: ColorSpace) -> &'static str {
+    match space {
+        ColorSpace::Srgb => "srgb",
+        ColorSpace::SrgbLinear => "srgb-linear",
+        ColorSpace::Xyz => "xyz",
+        ColorSpace::Lab => "lab",
+        ColorSpace::Lch => "lch",
+        ColorSpace::Oklab => "oklab",
+        ColorSpace::Oklch => "oklch",
+        ColorSpace::Hsl => "hsl",
+        ColorSpace::Hwb => "hwb",
+    }
+}
+
+fn parse_space(name: &str) -> Option<ColorSpace> {
+    match name {
+        "srgb" => Some(ColorSpace::Srgb),
+        "srgb-linear" => Some(ColorSpace::SrgbLinear),
+        "xyz" | "xyz-d65" => Some(ColorSpace::Xyz),
+        "lab" => Some(ColorSpace::Lab),
+        "lch" => Some(ColorSpace::Lch),
+        "oklab" => Some(ColorSpace::Oklab),
+        "oklch" => Some(ColorSpace::Oklch),
+        "hsl" => Some(ColorSpace::Hsl),
+        "hwb" => Some(ColorSpace::Hwb),
+        _ => None,
+    }
+}
+
+fn hue_name(hue: HueInterpolation) -> &'static str {
+    match hue {
+        HueInterpolation::Shorter => "shorter",
+        HueInterpolation::Longer => "longer",
+        HueInterpolation::Increasing => "increasing",
+        HueInterpolation::Decreasing => "decreasing",
+    }
+}
+
+fn parse_hue(name: &str) -> Option<HueInterpolation> {
+    match name {
+        "shorter" => Some(HueInterpolation::Shorter),
+        "longer" => Some(HueInterpolation::Longer),
+        "increasing" => Some(HueInterpolation::Increasing),
+        "decreasing" => Some(HueInterpolation::Decreasing),
+        _ => None,
+    }
+}
+
+/// Format a 0.0 to 1.0 position as a percentage with at most two decimals
+fn format_position(position: f32) -> String {
+    let percent = (position * 10000.0).round() / 100.0;
+    format!("{}%", if percent == 0.0 { 0.0 } else { percent })
+}
+
+impl CssGradient {
+    /// Parse a CSS gradient function, including `repeating-` variants
+    ///
+    /// Stop positions may be percentages (or angles for conic gradients);
+    /// lengths cannot be resolved without a box size and are rejected. Without
+    /// an `in <space>` clause, gradients of legacy sRGB colors interpolate in
+    /// sRGB and all others in Oklab, as CSS specifies.
+    pub fn parse(css: &str) -> Result<Self, ColorError> {
+        let css = css.trim();
+        let open = css.find('(').ok_or(ColorError::InvalidGradient)?;
+        let body = css[open + 1..]
+            .strip_suffix(')')
+            .ok_or(ColorError::InvalidGradient)?;
+
+        let name = css[..open].trim().to_ascii_lowercase();
+        let (repeating, name) = match name.strip_prefix("repeating-") {
+            Some(rest) => (true, rest),
+            None => (false, name.as_str()),
+        };
+        let kind = match name {
+            "linear-gradient" => GradientKind::Linear,
+            "radial-gradient" => GradientKind::Radial,
+            "conic-gradient" => GradientKind::Conic,
+            _ => return Err(ColorError::InvalidGradient),
+        };
+
+        let mut args = split_top_level(body, ',');
+        let mut prelude = String::new();
+        let mut interpolation = None;
+
+        let first_token = args
+            .first()
+            .and_then(|arg| split_tokens(arg).first().copied());
+        if first_token.is_some_and(|token| parse_color(token).is_err()) {
+            (prelude, interpolation) = parse_prelude(args.remove(0))?;
+        }
+
+        let mut stops: Vec<ColorStop> = Vec::new();
+        let mut all_legacy = true;
+        let mut hint_allowed = false;
+
+        for arg in args {
+            let tokens = split_tokens(arg);
+            match tokens.as_slice() {
+                [position] if parse_color(position).is_err() => {
+                    let hint = parse_position(position, kind)?;
+                    let previous = stops.last_mut().filter(|_| hint_allowed);
+                    previous.ok_or(ColorError::InvalidGradient)?.hint = Some(hint);
+                    hint_allowed = false;
+                }
+                [color, positions @ ..] if positions.len() <= 2 => {
+                    let (color, legacy) =
+                        parse_color(color).map_err(|_| ColorError::InvalidGradient)?;
+                    all_legacy &= legacy;
+                    if positions.is_empty() {
+                        stops.push(ColorStop::auto(&color));
+                    }
+                    for position in positions {
+                        stops.push(ColorStop::new(&color, parse_position(position, kind)?));
+                    }
+                    hint_allowed = true;
+                }
+                _ => return Err(ColorError::InvalidGradient),
+            }
+        }
+
+        // A hint must sit between two color stops
+        if stops.len() < 2 || !hint_allowed {
+            return Err(ColorError::InvalidGradient);
+        }
+
+        let (space, hue) = interpolation.unwrap_or(if all_legacy {
+            (ColorSpace::Srgb, HueInterpolation::Shorter)
+        } else {
+            (ColorSpace::Oklab, HueInterpolation::Shorter)
+        });
+
+        Ok(CssGradient {
+            kind,
+            repeating,
+            prelude,
+            gradient: Gradient::new(stops)
+                .with_space(space)
+                .with_hue_interpolation(hue),
+        })
+    }
+
+    /// Serialize to CSS, with an `in <space>` clause unless interpolating in sRGB
+    pub fn to_css(&self) -> String {
+        let mut prelude = self.prelude.clone();
+
+        let space = self.gradient.space();
+        if space != ColorSpace::Srgb {
+            let mut method = format!("in {}", space_name(space));
+            let hue = self.gradient.hue_interpolation();
+            if space.hue_index().is_some() && hue != HueInterpolation::Shorter {
+                method.push_str(&format!(" {} hue", hue_name(hue)));
+            }
+            if !prelude.is_empty() {
+                prelude.push(' ');
+            }
+            prelude.push_str(&method);
+        }
+
+        let stops = self.gradient.stops();
+        let mut args = Vec::new();
+        let mut i = 0;
+        while i < stops.len() {
+            let stop = &stops[i];
+            let mut arg = stop.color.to_css();
+            if let Some(position) = stop.position {
+                arg.push(' ');
+                arg.push_str(&format_position(position));
+
+                // Merge a following stop of the same color into a double-position stop
+                let next = stops.get(i + 1);
+                if let Some(next_position) = next
+                    .filter(|next| stop.hint.is_none() && next.color == stop.color)
+                    .and_then(|next| next.position)
+                {
+                    arg.push(' ');
+                    arg.push_str(&format_position(next_position));
+                    i += 1;
+                }
+            }
+            args.push(arg);
+
+            if let Some(hint) = stops[i].hint {
+                args.push(format_position(hint));
+            }
+            i += 1;
+        }
+
+        self.function(&prelude, &args)
+    }
+
+    /// Serialize to CSS using only sRGB interpolation
+    ///
+    /// Each segment between stops is sampled into `steps` sRGB stops so that
+    /// browsers without `in oklch` support render the perceptual gradient.
+    pub fn to_css_baked(&self, steps: usize) -> String {
+        let steps = steps.max(1);
+        let positions = self.gradient.resolved_positions();
+        let mut points = Vec::new();
+
+        for i in 0..positions.len().saturating_sub(1) {
+            let (start, end) = (positions[i], positions[i + 1]);
+            if end <= start {
+                continue;
+            }
+            for k in 0..=steps {
+                let position = start + (end - start) * k as f32 / steps as f32;
+                let color = self.gradient.sample_segment(i, &positions, position);
+                let arg = format!("{} {}", color.to_css(), format_position(position));
+                if points.last() != Some(&arg) {
+                    points.push(arg);
+                }
+            }
+        }
+
+        if points.is_empty() {
+            let stops = self.gradient.stops().iter().zip(&positions);
+            points = stops
+                .map(|(stop, &p)| format!("{} {}", stop.color.to_css(), format_position(p)))
+                .collect();
+        }
+
+        self.function(&self.prelude, &points)
+    }
+
+    fn function(&self, prelude: &str, args: &[String]) -> String {
+        let name = self.kind.function_name();
+        let repeating = if self.repeating { "repeating-" } else { "" };
+        let prelude = if prelude.is_empty() {
+            String::new()
+        } else {
+            format!("{}, ", prelude)
+        };
+        format!("{}{}({}{})", repeating, name, prelude, args.join(", "))
+    }
+}
+
+/// Split the `in <space> [<hue> hue]` clause from the gradient geometry
+fn parse_prelude(
+    prelude: &str,
+) -> Result<(String, Option<(ColorSpace, HueInterpolation)>), ColorError> {
+    let lower = prelude.to_ascii_lowercase();
+    let tokens = split_tokens(&lower);
+    let mut geometry = Vec::new();
+    let mut interpolation = None;
+
+    let mut i = 0;
+    while i < tokens.len() {
+        if tokens[i] != "in" {
+            geometry.push(split_tokens(prelude)[i]);
+            i += 1;
+            continue;
+        }
+
+        let space = tokens
+            .get(i + 1)
+            .and_then(|name| parse_space(name))
+            .ok_or(ColorError::InvalidGradient)?;
+        let mut hue = HueInterpolation::Shorter;
+        i += 2;
+
+        if let (Some(method), Some(&"hue")) =
+            (tokens.get(i).and_then(|t| parse_hue(t)), tokens.get(i + 1))
+        {
+            if space.hue_index().is_none() {
+                return Err(ColorError::InvalidGradient);
+            }
+            hue = method;
+            i += 2;
+        }
+        interpolation = Some((space, hue));
+    }
+
+    Ok((geometry.join(" "), interpolation))
+}
+
+/// A stop or hint position as a fraction of the gradient line
+fn parse_position(token: &str, kind: GradientKind) -> Result<f32, ColorError> {
+    if let Some(percent) = token.strip_suffix('%') {
+        return percent
+            .parse::<f32>()
+            .map(|p| p / 100.0)
+            .map_err(|_| ColorError::InvalidGradient);
+    }
+    if token == "0" {
+        return Ok(0.0);
+    }
+    match kind {
+        GradientKind::Conic => parse_angle(token)
+            .filter(|_| token.ends_with(|c: char| c.is_ascii_alphabetic()))
+            .map(|degrees| degrees / 360.0)
+            .ok_or(ColorError::InvalidGradient),
+        _ => Err(ColorError::InvalidGradient),
+    }
+}
+
+impl fmt::Display for CssGradient {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.to_css())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Color;
+
+    #[test]
+    fn test_parse_linear_gradient() {
+        let parsed =
+            CssGradient::parse("linear-gradient(to right, red, #00F 40% 60%, 80%, rgb(0 255 0))")
+                .unwrap();
+        assert_eq!(parsed.kind, GradientKind::Linear);
+        assert!(!parsed.repeating);
+        assert_eq!(parsed.prelude, "to right");
+        assert_eq!(parsed.gradient.space(), ColorSpace::Srgb);
+
+        let stops = parsed.gradient.stops();
+        assert_eq!(stops.len(), 4);
+        assert_eq!(stops[0].position, None);
+        assert_eq!(stops[1].color, Color::BLUE);
+        assert_eq!(stops[1].position, Some(0.4));
+        assert_eq!(stops[2].position, Some(0.6));
+        assert_eq!(stops[2].hint, Some(0.8));
+        assert_eq!(stops[3].color, Color::GREEN);
+    }
+
+    #[test]
+    fn test_parse_interpolation_method() {
+        let parsed = CssGradient::parse(
+            "repeating-radial-gradient(circle at top in oklch longer hue, red, blue 25%)",
+        )
+        .unwrap();
+        assert_eq!(parsed.kind, GradientKind::Radial);
+        assert!(parsed.repeating);
+        assert_eq!(parsed.prelude, "circle at top");
+        assert_eq!(parsed.gradient.space(), ColorSpace::Oklch);
+        assert_eq!(
+            parsed.gradient.hue_interpolation(),
+            HueInterpolation::Longer
+        );
+
+        let parsed = CssGradient::parse("linear-gradient(oklch(70% 0.1 200), red)").unwrap();
+        assert_eq!(parsed.gradient.space(), ColorSpace::Oklab);
+
+        let parsed =
+            CssGradient::parse("conic-gradient(from 90deg, red 0.25turn, blue 180deg)").unwrap();
+        assert_eq!(parsed.gradient.stops()[0].position, Some(0.25));
+        assert_eq!(parsed.gradient.stops()[1].position, Some(0.5));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let invalid = [
+            "linear-gradient(red)",
+            "linear-gradient(red, 50%)",
+            "linear-gradient(red, 20%, 50%, blue)",
+            "linear-gradient(red 10px, blue)",
+            "linear-gradient(in srgb longer hue, red, blue)",
+            "diagonal-gradient(red, blue)",
+        ];
+        for css in invalid {
+            assert_eq!(
+                CssGradient::parse(css),
+                Err(ColorError::InvalidGradient),
+                "{}",
+                css
+            );
+        }
+    }
+
+    #[test]
+    fn test_serialize() {
+        let css = "linear-gradient(45deg in oklch decreasing hue, #FF0000, #0000FF 40% 60%, 80%, #00FF00)";
+        assert_eq!(CssGradient::parse(css).unwrap().to_css(), css);
+
+        let css = "radial-gradient(#FF000080 0%, #0000FF 100%)";
+        assert_eq!(CssGradient::parse(css).unwrap().to_string(), css);
+    }
+
+    #[test]
+    fn test_serialize_baked() {
+        let parsed = CssGradient::parse("linear-gradient(to right in oklch, red, blue)").unwrap();
+        let baked = parsed.to_css_baked(4);
+        assert!(baked.starts_with("linear-gradient(to right, #FF0000 0%, "));
+        assert!(baked.ends_with(", #0000FF 100%)"));
+        assert!(!baked.contains(" in "));
+        assert_eq!(baked.matches('#').count(), 5);
+
+        // Hard edges survive baking
+        let parsed = CssGradient::parse("linear-gradient(in oklab, red 50%, blue 50%)").unwrap();
+        assert_eq!(
+            parsed.to_css_baked(8),
+            "linear-gradient(#FF0000 50%, #0000FF 50%)"
+        );
+    }
+}
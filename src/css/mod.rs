@@ -0,0 +1,326 @@
+//! CSS color and gradient syntax
+
+mod gradient;
+mod named;
+
+pub use gradient::{CssGradient, GradientKind};
+
+use crate::{Color, ColorError, ColorSpace};
+use named::NAMED_COLORS;
+
+/// Split at `separator` outside of parentheses, trimming each part
+pub(crate) fn split_top_level(s: &str, separator: char) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut depth = 0usize;
+    let mut start = 0;
+
+    for (i, c) in s.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth = depth.saturating_sub(1),
+            c if c == separator && depth == 0 => {
+                parts.push(s[start..i].trim());
+                start = i + c.len_utf8();
+            }
+            _ => {}
+        }
+    }
+    parts.push(s[start..].trim());
+    parts
+}
+
+/// Split at whitespace outside of parentheses, dropping empty parts
+pub(crate) fn split_tokens(s: &str) -> Vec<&str> {
+    let mut tokens = Vec::new();
+    let mut depth = 0usize;
+    let mut start = None;
+
+    for (i, c) in s.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth = depth.saturating_sub(1),
+            c if c.is_whitespace() && depth == 0 => {
+                if let Some(begin) = start.take() {
+                    tokens.push(&s[begin..i]);
+                }
+                continue;
+            }
+            _ => {}
+        }
+        start.get_or_insert(i);
+    }
+    if let Some(begin) = start {
+        tokens.push(&s[begin..]);
+    }
+    tokens
+}
+
+/// A numeric component: `(value, is_percentage)`, with `none` as zero
+fn parse_component(token: &str) -> Option<(f32, bool)> {
+    if token.eq_ignore_ascii_case("none") {
+        return Some((0.0, false));
+    }
+    match token.strip_suffix('%') {
+        Some(number) => number.parse().ok().map(|v| (v, true)),
+        None => token.parse().ok().map(|v| (v, false)),
+    }
+}
+
+/// A hue or angle in degrees (bare numbers are degrees)
+pub(crate) fn parse_angle(token: &str) -> Option<f32> {
+    let token = token.to_ascii_lowercase();
+    if token == "none" {
+        return Some(0.0);
+    }
+
+    let units = [
+        ("deg", 1.0),
+        ("grad", 0.9),
+        ("rad", 180.0 / std::f32::consts::PI),
+        ("turn", 360.0),
+    ];
+    for (unit, scale) in units {
+        if let Some(number) = token.strip_suffix(unit) {
+            return number.parse::<f32>().ok().map(|v| v * scale);
+        }
+    }
+    token.parse().ok()
+}
+
+/// Parse a CSS color, also reporting whether it used legacy sRGB syntax
+pub(crate) fn parse_color(css: &str) -> Result<(Color, bool), ColorError> {
+    let css = css.trim().to_ascii_lowercase();
+
+    if let Some(hex) = css.strip_prefix('#') {
+        return parse_hex(hex).map(|color| (color, true));
+    }
+    if css == "transparent" {
+        return Ok((Color::from_rgba(0, 0, 0, 0.0), true));
+    }
+    if let Ok(i) = NAMED_COLORS.binary_search_by(|(name, _)| name.cmp(&css.as_str())) {
+        let [r, g, b] = NAMED_COLORS[i].1;
+        return Ok((Color::from_rgb(r, g, b), true));
+    }
+
+    let open = css.find('(').ok_or(ColorError::InvalidCssColor)?;
+    let args = css[open + 1..]
+        .strip_suffix(')')
+        .ok_or(ColorError::InvalidCssColor)?;
+    let name = css[..open].trim();
+
+    let (channels, alpha) = split_arguments(args)?;
+    let alpha = match alpha {
+        Some(token) => {
+            let (value, percent) = parse_component(token).ok_or(ColorError::InvalidCssColor)?;
+            if percent { value / 100.0 } else { value }
+        }
+        None => 1.0,
+    };
+
+    let component = |i: usize| parse_component(channels[i]).ok_or(ColorError::InvalidCssColor);
+    let hue = |i: usize| parse_angle(channels[i]).ok_or(ColorError::InvalidCssColor);
+    // Scale a component, where 100% corresponds to `full`
+    let scaled = |i: usize, full: f32| {
+        component(i).map(|(v, percent)| if percent { v / 100.0 * full } else { v })
+    };
+
+    let (space, values, legacy) = match name {
+        "rgb" | "rgba" => {
+            let channel = |i: usize| {
+                component(i).map(|(v, percent)| if percent { v / 100.0 } else { v / 255.0 })
+            };
+            let [r, g, b] = [channel(0)?, channel(1)?, channel(2)?]
+                .map(|c| (c.clamp(0.0, 1.0) * 255.0).round() as u8);
+            return Ok((Color::from_rgba(r, g, b, alpha), true));
+        }
+        "hsl" | "hsla" => (
+            ColorSpace::Hsl,
+            [
+                hue(0)?,
+                scaled(1, 100.0)?.clamp(0.0, 100.0),
+                scaled(2, 100.0)?.clamp(0.0, 100.0),
+            ],
+            true,
+        ),
+        "hwb" => (
+            ColorSpace::Hwb,
+            [
+                hue(0)?,
+                scaled(1, 100.0)?.clamp(0.0, 100.0),
+                scaled(2, 100.0)?.clamp(0.0, 100.0),
+            ],
+            true,
+        ),
+        "lab" => (
+            ColorSpace::Lab,
+            [
+                scaled(0, 100.0)?.clamp(0.0, 100.0),
+                scaled(1, 125.0)?,
+                scaled(2, 125.0)?,
+            ],
+            false,
+        ),
+        "lch" => (
+            ColorSpace::Lch,
+            [
+                scaled(0, 100.0)?.clamp(0.0, 100.0),
+                scaled(1, 150.0)?.max(0.0),
+                hue(2)?,
+            ],
+            false,
+        ),
+        "oklab" => (
+            ColorSpace::Oklab,
+            [
+                scaled(0, 1.0)?.clamp(0.0, 1.0),
+                scaled(1, 0.4)?,
+                scaled(2, 0.4)?,
+            ],
+            false,
+        ),
+        "oklch" => (
+            ColorSpace::Oklch,
+            [
+                scaled(0, 1.0)?.clamp(0.0, 1.0),
+                scaled(1, 0.4)?.max(0.0),
+                hue(2)?,
+            ],
+            false,
+        ),
+        _ => return Err(ColorError::InvalidCssColor),
+    };
+
+    Ok((space.compose(values, alpha.clamp(0.0, 1.0)), legacy))
+}
+
+/// Split function arguments into three channels and an optional alpha
+fn split_arguments(args: &str) -> Result<(Vec<&str>, Option<&str>), ColorError> {
+    let (main, mut alpha) = match split_top_level(args, '/').as_slice() {
+        [main] => (*main, None),
+        [main, alpha] => (*main, Some(*alpha)),
+        _ => return Err(ColorError::InvalidCssColor),
+    };
+
+    let mut channels = if main.contains(',') {
+        split_top_level(main, ',')
+    } else {
+        split_tokens(main)
+    };
+    if alpha.is_none() && channels.len() == 4 {
+        alpha = channels.pop();
+    }
+
+    if channels.len() != 3 || alpha.is_some_and(str::is_empty) {
+        return Err(ColorError::InvalidCssColor);
+    }
+    Ok((channels, alpha))
+}
+
+fn parse_hex(hex: &str) -> Result<Color, ColorError> {
+    if !hex.is_ascii() {
+        return Err(ColorError::InvalidCssColor);
+    }
+    if hex.len() != 4 {
+        return Color::from_hex(hex).map_err(|_| ColorError::InvalidCssColor);
+    }
+
+    // #RGBA is valid in CSS but not accepted by `from_hex`
+    let digits: Vec<u8> = hex
+        .chars()
+        .map(|c| c.to_digit(16).map(|d| d as u8 * 17))
+        .collect::<Option<_>>()
+        .ok_or(ColorError::InvalidCssColor)?;
+    Ok(Color::from_rgba(
+        digits[0],
+        digits[1],
+        digits[2],
+        digits[3] as f32 / 255.0,
+    ))
+}
+
+impl Color {
+    /// Create a Color from any CSS color syntax
+    ///
+    /// Supports hex, named colors, `transparent` and the `rgb()`, `hsl()`,
+    /// `hwb()`, `lab()`, `lch()`, `oklab()` and `oklch()` functions.
+    pub fn from_css(css: &str) -> Result<Self, ColorError> {
+        parse_color(css).map(|(color, _)| color)
+    }
+
+    /// Convert to a CSS color string (hex, with alpha only when translucent)
+    pub fn to_css(&self) -> String {
+        if self.a >= 1.0 {
+            self.to_hex()
+        } else {
+            self.to_hex_alpha()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_css_hex_and_names() {
+        assert_eq!(
+            Color::from_css("#F53").unwrap(),
+            Color::from_rgb(255, 85, 51)
+        );
+        assert_eq!(
+            Color::from_css("#f538").unwrap(),
+            Color::from_rgba(255, 85, 51, 136.0 / 255.0)
+        );
+        assert_eq!(
+            Color::from_css("RebeccaPurple").unwrap(),
+            Color::from_rgb(102, 51, 153)
+        );
+        assert_eq!(Color::from_css("transparent").unwrap().a, 0.0);
+        assert_eq!(
+            Color::from_css("notacolor"),
+            Err(ColorError::InvalidCssColor)
+        );
+    }
+
+    #[test]
+    fn test_css_functions() {
+        let expected = Color::from_rgb(255, 87, 51);
+        assert_eq!(Color::from_css("rgb(255, 87, 51)").unwrap(), expected);
+        assert_eq!(Color::from_css("rgb(255 87 51)").unwrap(), expected);
+        assert_eq!(
+            Color::from_css("rgba(100%, 0%, 0%, 0.5)").unwrap(),
+            Color::from_rgba(255, 0, 0, 0.5)
+        );
+        assert_eq!(
+            Color::from_css("rgb(255 0 0 / 50%)").unwrap(),
+            Color::from_rgba(255, 0, 0, 0.5)
+        );
+        assert_eq!(
+            Color::from_css("hsl(120deg 100% 50%)").unwrap(),
+            Color::GREEN
+        );
+        assert_eq!(
+            Color::from_css("hsl(0.5turn, 100%, 50%)").unwrap(),
+            Color::CYAN
+        );
+        assert_eq!(Color::from_css("hwb(240 0% 0%)").unwrap(), Color::BLUE);
+        assert_eq!(
+            Color::from_css("oklch(62.8% 0.2577 29.23)").unwrap(),
+            Color::RED
+        );
+        assert_eq!(
+            Color::from_css("lab(54.29 80.8 69.89)").unwrap(),
+            Color::RED
+        );
+        assert_eq!(
+            Color::from_css("rgb(1, 2)"),
+            Err(ColorError::InvalidCssColor)
+        );
+    }
+
+    #[test]
+    fn test_to_css() {
+        assert_eq!(Color::RED.to_css(), "#FF0000");
+        assert_eq!(Color::from_rgba(255, 0, 0, 0.5).to_css(), "#FF000080");
+    }
+}
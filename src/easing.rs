@@ -0,0 +1,111 @@
+//! Easing curves for interpolation
+
+/// Easing curve applied to an interpolation parameter in `0.0..=1.0`
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Easing {
+    /// No easing
+    #[default]
+    Linear,
+    /// CSS `ease` (cubic-bezier(0.25, 0.1, 0.25, 1))
+    Ease,
+    /// CSS `ease-in` (cubic-bezier(0.42, 0, 1, 1))
+    EaseIn,
+    /// CSS `ease-out` (cubic-bezier(0, 0, 0.58, 1))
+    EaseOut,
+    /// CSS `ease-in-out` (cubic-bezier(0.42, 0, 0.58, 1))
+    EaseInOut,
+    /// Raise `t` to the given exponent
+    Power(f32),
+    /// CSS cubic-bezier(x1, y1, x2, y2)
+    CubicBezier(f32, f32, f32, f32),
+}
+
+impl Easing {
+    /// Apply the curve to `t`, which is clamped to `0.0..=1.0`
+    pub fn apply(&self, t: f32) -> f32 {
+        let t = t.clamp(0.0, 1.0);
+        match *self {
+            Easing::Linear => t,
+            Easing::Ease => cubic_bezier(0.25, 0.1, 0.25, 1.0, t),
+            Easing::EaseIn => cubic_bezier(0.42, 0.0, 1.0, 1.0, t),
+            Easing::EaseOut => cubic_bezier(0.0, 0.0, 0.58, 1.0, t),
+            Easing::EaseInOut => cubic_bezier(0.42, 0.0, 0.58, 1.0, t),
+            Easing::Power(exponent) => t.powf(exponent.max(0.0)),
+            Easing::CubicBezier(x1, y1, x2, y2) => {
+                cubic_bezier(x1.clamp(0.0, 1.0), y1, x2.clamp(0.0, 1.0), y2, t)
+            }
+        }
+    }
+}
+
+/// Evaluate a cubic Bézier timing function with endpoints (0, 0) and (1, 1)
+fn cubic_bezier(x1: f32, y1: f32, x2: f32, y2: f32, x: f32) -> f32 {
+    let bezier = |p1: f32, p2: f32, s: f32| {
+        let inv = 1.0 - s;
+        3.0 * inv * inv * s * p1 + 3.0 * inv * s * s * p2 + s * s * s
+    };
+    let slope = |p1: f32, p2: f32, s: f32| {
+        let inv = 1.0 - s;
+        3.0 * inv * inv * p1 + 6.0 * inv * s * (p2 - p1) + 3.0 * s * s * (1.0 - p2)
+    };
+
+    // Newton's method first, falling back to bisection when the slope is flat
+    let mut s = x;
+    for _ in 0..8 {
+        let error = bezier(x1, x2, s) - x;
+        if error.abs() < 1e-6 {
+            return bezier(y1, y2, s);
+        }
+        let d = slope(x1, x2, s);
+        if d.abs() < 1e-6 {
+            break;
+        }
+        s -= error / d;
+    }
+
+    let (mut lo, mut hi) = (0.0, 1.0);
+    s = x;
+    for _ in 0..32 {
+        let value = bezier(x1, x2, s);
+        if (value - x).abs() < 1e-6 {
+            break;
+        }
+        if value < x {
+            lo = s;
+        } else {
+            hi = s;
+        }
+        s = (lo + hi) / 2.0;
+    }
+    bezier(y1, y2, s)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_easing_endpoints() {
+        let curves = [
+            Easing::Linear,
+            Easing::Ease,
+            Easing::EaseIn,
+            Easing::EaseOut,
+            Easing::EaseInOut,
+            Easing::Power(2.0),
+            Easing::CubicBezier(0.1, 0.7, 1.0, 0.1),
+        ];
+        for curve in curves {
+            assert!(curve.apply(0.0).abs() < 1e-4);
+            assert!((curve.apply(1.0) - 1.0).abs() < 1e-4);
+        }
+    }
+
+    #[test]
+    fn test_easing_shape() {
+        assert!(Easing::EaseIn.apply(0.5) < 0.5);
+        assert!(Easing::EaseOut.apply(0.5) > 0.5);
+        assert!((Easing::EaseInOut.apply(0.5) - 0.5).abs() < 1e-3);
+        assert!((Easing::Power(2.0).apply(0.5) - 0.25).abs() < 1e-6);
+    }
+}
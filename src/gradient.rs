@@ -0,0 +1,283 @@
+//! Multi-stop color gradients
+
+use crate::{Color, ColorSpace, Easing, HueInterpolation};
+
+/// A color stop in a gradient
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColorStop {
+    pub color: Color,
+    pub position: Option<f32>, // 0.0 to 1.0, `None` to place automatically
+    pub easing: Easing,        // Easing of the segment after this stop
+    pub hint: Option<f32>,     // Midpoint of the segment after this stop (0.0 to 1.0)
+}
+
+/// A gradient through color stops, sampled like CSS gradients
+#[derive(Debug, Clone, PartialEq)]
+pub struct Gradient {
+    stops: Vec<ColorStop>,
+    space: ColorSpace,
+    hue: HueInterpolation,
+}
+
+impl ColorStop {
+    /// Create a stop at a position (0.0 to 1.0)
+    pub fn new(color: &Color, position: f32) -> Self {
+        ColorStop {
+            color: color.clone(),
+            position: Some(position),
+            easing: Easing::Linear,
+            hint: None,
+        }
+    }
+
+    /// Create a stop whose position is spaced evenly between its neighbors
+    pub fn auto(color: &Color) -> Self {
+        ColorStop {
+            color: color.clone(),
+            position: None,
+            easing: Easing::Linear,
+            hint: None,
+        }
+    }
+
+    /// Set the easing of the segment from this stop to the next one
+    pub fn with_easing(mut self, easing: Easing) -> Self {
+        self.easing = easing;
+        self
+    }
+
+    /// Set a CSS color hint: the position where the segment after this stop
+    /// reaches its halfway color (takes precedence over the easing)
+    pub fn with_hint(mut self, hint: f32) -> Self {
+        self.hint = Some(hint);
+        self
+    }
+}
+
+impl Gradient {
+    /// Create a gradient from stops, interpolating in Oklab like CSS
+    pub fn new(stops: Vec<ColorStop>) -> Self {
+        Gradient {
+            stops,
+            space: ColorSpace::Oklab,
+            hue: HueInterpolation::Shorter,
+        }
+    }
+
+    /// Create a gradient with evenly spaced colors
+    pub fn from_colors(colors: &[Color]) -> Self {
+        Gradient::new(colors.iter().map(ColorStop::auto).collect())
+    }
+
+    /// Set the color space used for interpolation
+    pub fn with_space(mut self, space: ColorSpace) -> Self {
+        self.space = space;
+        self
+    }
+
+    /// Set how hues are interpolated in polar color spaces
+    pub fn with_hue_interpolation(mut self, hue: HueInterpolation) -> Self {
+        self.hue = hue;
+        self
+    }
+
+    /// The color stops as given
+    pub fn stops(&self) -> &[ColorStop] {
+        &self.stops
+    }
+
+    /// The interpolation color space
+    pub fn space(&self) -> ColorSpace {
+        self.space
+    }
+
+    /// The hue interpolation method
+    pub fn hue_interpolation(&self) -> HueInterpolation {
+        self.hue
+    }
+
+    /// Stop positions after CSS color stop fixup
+    ///
+    /// The first and last stops default to 0.0 and 1.0, a stop before a
+    /// previous one is moved up to it, and runs of unpositioned stops are
+    /// spaced evenly between their neighbors.
+    pub fn resolved_positions(&self) -> Vec<f32> {
+        let count = self.stops.len();
+        let mut positions: Vec<Option<f32>> = self.stops.iter().map(|s| s.position).collect();
+        if count == 0 {
+            return Vec::new();
+        }
+
+        positions[0].get_or_insert(0.0);
+        positions[count - 1].get_or_insert(1.0);
+
+        let mut max = f32::NEG_INFINITY;
+        for position in positions.iter_mut().flatten() {
+            max = max.max(*position);
+            *position = max;
+        }
+
+        let mut resolved = Vec::with_capacity(count);
+        let mut i = 0;
+        while i < count {
+            match positions[i] {
+                Some(position) => {
+                    resolved.push(position);
+                    i += 1;
+                }
+                None => {
+                    let start = resolved[i - 1];
+                    let end_index = (i..count).find(|&j| positions[j].is_some()).unwrap();
+                    let end = positions[end_index].unwrap();
+                    let steps = (end_index - i + 1) as f32;
+                    for k in i..end_index {
+                        resolved.push(start + (end - start) * (k - i + 1) as f32 / steps);
+                    }
+                    i = end_index;
+                }
+            }
+        }
+
+        resolved
+    }
+
+    /// Sample the gradient at `t`, clamping to the first and last stops
+    pub fn sample(&self, t: f32) -> Color {
+        let positions = self.resolved_positions();
+        let (first, last) = match (self.stops.first(), self.stops.last()) {
+            (Some(first), Some(last)) => (first, last),
+            _ => return Color::from_rgba(0, 0, 0, 0.0),
+        };
+
+        if t <= positions[0] {
+            return first.color.clone();
+        }
+
+        // The last stop at or before `t`, so shared positions form hard edges
+        let i = positions.iter().rposition(|&p| p <= t).unwrap_or(0);
+        if i == positions.len() - 1 {
+            return last.color.clone();
+        }
+
+        self.sample_segment(i, &positions, t)
+    }
+
+    /// Sample the segment from stop `i` to stop `i + 1` at `t`
+    pub(crate) fn sample_segment(&self, i: usize, positions: &[f32], t: f32) -> Color {
+        let (start, end) = (positions[i], positions[i + 1]);
+        let progress = if end > start {
+            ((t - start) / (end - start)).clamp(0.0, 1.0)
+        } else {
+            1.0
+        };
+
+        let local = match self.stops[i].hint {
+            Some(hint) => apply_hint((hint - start) / (end - start), progress),
+            None => self.stops[i].easing.apply(progress),
+        };
+        self.space.interpolate(
+            &self.stops[i].color,
+            &self.stops[i + 1].color,
+            local,
+            self.hue,
+        )
+    }
+
+    /// `n` evenly spaced colors from 0.0 to 1.0 inclusive
+    pub fn take(&self, n: usize) -> Vec<Color> {
+        match n {
+            0 => Vec::new(),
+            1 => vec![self.sample(0.0)],
+            _ => (0..n)
+                .map(|i| self.sample(i as f32 / (n - 1) as f32))
+                .collect(),
+        }
+    }
+}
+
+/// CSS color hint curve: progress `p` passes 0.5 at relative midpoint `h`
+fn apply_hint(h: f32, p: f32) -> f32 {
+    if h <= 0.0 {
+        if p > 0.0 { 1.0 } else { 0.0 }
+    } else if h >= 1.0 {
+        if p < 1.0 { 0.0 } else { 1.0 }
+    } else {
+        p.powf(0.5f32.ln() / h.ln())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stop_fixup() {
+        let gradient = Gradient::new(vec![
+            ColorStop::auto(&Color::RED),
+            ColorStop::auto(&Color::GREEN),
+            ColorStop::auto(&Color::BLUE),
+            ColorStop::new(&Color::WHITE, 0.9),
+            ColorStop::new(&Color::BLACK, 0.5),
+            ColorStop::auto(&Color::RED),
+        ]);
+        let positions = gradient.resolved_positions();
+        let expected = [0.0, 0.3, 0.6, 0.9, 0.9, 1.0];
+        for (p, e) in positions.iter().zip(expected) {
+            assert!((p - e).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_sample() {
+        let gradient =
+            Gradient::from_colors(&[Color::RED, Color::BLUE]).with_space(ColorSpace::Srgb);
+        assert_eq!(gradient.sample(-1.0), Color::RED);
+        assert_eq!(gradient.sample(0.5), Color::from_rgb(128, 0, 128));
+        assert_eq!(gradient.sample(2.0), Color::BLUE);
+
+        let colors = gradient.take(3);
+        assert_eq!(
+            colors,
+            [Color::RED, Color::from_rgb(128, 0, 128), Color::BLUE]
+        );
+        assert!(gradient.take(0).is_empty());
+    }
+
+    #[test]
+    fn test_hard_stop() {
+        let gradient = Gradient::new(vec![
+            ColorStop::new(&Color::RED, 0.0),
+            ColorStop::new(&Color::RED, 0.5),
+            ColorStop::new(&Color::BLUE, 0.5),
+            ColorStop::new(&Color::BLUE, 1.0),
+        ]);
+        assert_eq!(gradient.sample(0.49), Color::RED);
+        assert_eq!(gradient.sample(0.5), Color::BLUE);
+    }
+
+    #[test]
+    fn test_segment_easing_and_hue() {
+        let gradient = Gradient::new(vec![
+            ColorStop::new(&Color::BLACK, 0.0).with_easing(Easing::Power(2.0)),
+            ColorStop::new(&Color::WHITE, 1.0),
+        ])
+        .with_space(ColorSpace::SrgbLinear);
+        assert_eq!(gradient.sample(0.5), Color::from_linear_rgb([0.25; 3], 1.0));
+
+        let gradient = Gradient::from_colors(&[Color::RED, Color::BLUE])
+            .with_space(ColorSpace::Hsl)
+            .with_hue_interpolation(HueInterpolation::Longer);
+        assert_eq!(gradient.sample(0.5), Color::GREEN);
+    }
+
+    #[test]
+    fn test_color_hint() {
+        let gradient = Gradient::new(vec![
+            ColorStop::new(&Color::BLACK, 0.0).with_hint(0.25),
+            ColorStop::new(&Color::WHITE, 1.0),
+        ])
+        .with_space(ColorSpace::Srgb);
+        assert_eq!(gradient.sample(0.25), Color::from_rgb(128, 128, 128));
+        assert!(gradient.sample(0.5).r > 128);
+    }
+}
@@ -0,0 +1,180 @@
+//! HTML rendering of formatted text
+
+use crate::minecraft::{color_name, legacy_color};
+use crate::{Color, Style, Text};
+
+/// Options for [`Text::to_html`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct HtmlOptions {
+    pub legacy_classes: bool, // Use `mc-red` style classes for the 16 legacy colors
+    pub text_shadow: bool,    // Add the shadow vanilla draws behind text
+}
+
+impl HtmlOptions {
+    /// Inline colors and no shadow
+    pub fn new() -> Self {
+        HtmlOptions::default()
+    }
+
+    /// Use classes such as `mc-dark-red` instead of inline legacy colors
+    pub fn with_legacy_classes(mut self) -> Self {
+        self.legacy_classes = true;
+        self
+    }
+
+    /// Add a `text-shadow` in the color vanilla draws it
+    pub fn with_text_shadow(mut self) -> Self {
+        self.text_shadow = true;
+        self
+    }
+
+    /// CSS rules for the legacy color classes, e.g. `.mc-red{color:#FF5555}`
+    pub fn legacy_stylesheet() -> String {
+        "0123456789abcdef"
+            .chars()
+            .filter_map(legacy_color)
+            .map(|color| {
+                let name = color_name(&color).unwrap().replace('_', "-");
+                format!(".mc-{}{{color:{}}}\n", name, color.to_hex())
+            })
+            .collect()
+    }
+}
+
+impl Color {
+    /// The color vanilla draws text shadows in: each channel at a quarter
+    pub fn minecraft_shadow(&self) -> Color {
+        Color::from_rgba(self.r / 4, self.g / 4, self.b / 4, self.a)
+    }
+}
+
+/// Escape text for use in HTML content or attributes
+fn escape(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => result.push_str("&amp;"),
+            '<' => result.push_str("&lt;"),
+            '>' => result.push_str("&gt;"),
+            '"' => result.push_str("&quot;"),
+            '\'' => result.push_str("&#39;"),
+            '\n' => result.push_str("<br>"),
+            c => result.push(c),
+        }
+    }
+    result
+}
+
+/// The classes and inline declarations for a style
+fn style_attributes(style: &Style, options: HtmlOptions) -> (Vec<String>, Vec<String>) {
+    let mut classes = Vec::new();
+    let mut declarations = Vec::new();
+
+    if let Some(color) = &style.color {
+        match color_name(color).filter(|_| options.legacy_classes) {
+            Some(name) => classes.push(format!("mc-{}", name.replace('_', "-"))),
+            None => declarations.push(format!("color:{}", color.to_hex())),
+        }
+    }
+    if let Some(background) = &style.background {
+        declarations.push(format!("background-color:{}", background.to_hex()));
+    }
+    if style.bold {
+        declarations.push("font-weight:bold".to_string());
+    }
+    if style.italic {
+        declarations.push("font-style:italic".to_string());
+    }
+    let lines: Vec<&str> = [
+        (style.underlined, "underline"),
+        (style.strikethrough, "line-through"),
+    ]
+    .iter()
+    .filter(|(enabled, _)| *enabled)
+    .map(|(_, line)| *line)
+    .collect();
+    if !lines.is_empty() {
+        declarations.push(format!("text-decoration:{}", lines.join(" ")));
+    }
+    // Obfuscation needs scripting, so it is only marked
+    if style.obfuscated {
+        classes.push("mc-obfuscated".to_string());
+    }
+    if options.text_shadow {
+        // Uncolored text is white in vanilla chat
+        let color = style.color.as_ref().unwrap_or(&Color::WHITE);
+        let shadow = color.minecraft_shadow().to_hex();
+        declarations.push(format!("text-shadow:0.125em 0.125em {}", shadow));
+    }
+
+    (classes, declarations)
+}
+
+impl Text {
+    /// Render to escaped HTML, one `<span>` per styled span
+    pub fn to_html(&self, options: HtmlOptions) -> String {
+        let mut result = String::new();
+
+        for span in &self.spans {
+            let (classes, declarations) = style_attributes(&span.style, options);
+            if classes.is_empty() && declarations.is_empty() {
+                result.push_str(&escape(&span.text));
+                continue;
+            }
+
+            result.push_str("<span");
+            if !classes.is_empty() {
+                result.push_str(&format!(" class=\"{}\"", classes.join(" ")));
+            }
+            if !declarations.is_empty() {
+                result.push_str(&format!(" style=\"{}\"", declarations.join(";")));
+            }
+            result.push_str(&format!(">{}</span>", escape(&span.text)));
+        }
+
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_html() {
+        let text = Text::from_legacy("§c§lHi §r<b>&#FF5733&n&mx\"");
+        assert_eq!(
+            text.to_html(HtmlOptions::new()),
+            "<span style=\"color:#FF5555;font-weight:bold\">Hi </span>&lt;b&gt;\
+             <span style=\"color:#FF5733;text-decoration:underline line-through\">x&quot;</span>"
+        );
+        assert_eq!(
+            Text::from_legacy("&4&kA\nB").to_html(HtmlOptions::new().with_legacy_classes()),
+            "<span class=\"mc-dark-red mc-obfuscated\">A<br>B</span>"
+        );
+
+        assert_eq!(
+            Text::from_ansi("\x1b[30;47mA").to_html(HtmlOptions::new()),
+            "<span style=\"color:#000000;background-color:#AAAAAA\">A</span>"
+        );
+
+        let stylesheet = HtmlOptions::legacy_stylesheet();
+        assert_eq!(stylesheet.lines().count(), 16);
+        assert!(stylesheet.contains(".mc-light-purple{color:#FF55FF}"));
+    }
+
+    #[test]
+    fn test_text_shadow() {
+        assert_eq!(
+            Color::MC_RED.minecraft_shadow(),
+            Color::from_rgb(63, 21, 21)
+        );
+
+        let text = Text::from_legacy("&eA&rB");
+        assert_eq!(
+            text.to_html(HtmlOptions::new().with_legacy_classes().with_text_shadow()),
+            "<span class=\"mc-yellow\" style=\"text-shadow:0.125em 0.125em #3F3F15\">A</span>\
+             <span style=\"text-shadow:0.125em 0.125em #3F3F3F\">B</span>"
+        );
+    }
+}
@@ -15,6 +15,39 @@
 
 use std::fmt;
 
+mod ansi;
+mod composite;
+mod css;
+mod easing;
+mod gradient;
+mod html;
+mod json;
+mod minecraft;
+mod mix;
+mod scale;
+mod space;
+mod text;
+
+pub use ansi::{AnsiPalette, ColorDepth, ObfuscatedFallback};
+pub use composite::{BlendMode, CompositeOp, Premultiplied};
+pub use css::{CssGradient, GradientKind};
+pub use easing::Easing;
+pub use gradient::{ColorStop, Gradient};
+pub use html::HtmlOptions;
+pub use minecraft::{
+    Biome, COLORMAP_SIZE, Colormap, DATA_VERSION_1_20_5, DATA_VERSION_1_21_4, DATA_VERSION_1_21_5,
+    DyeColor, DyedBlock, Edition, GrassModifier, HexFormat, LeatherRecipe, MAP_SIZE,
+    MAX_DYES_PER_CRAFT, MalformedCode, MalformedCodeKind, MapArtOptions, MapData, Motd, TextArea,
+    center_legacy, downsample_legacy_codes, dye_leather, give_dyed_item, give_potion,
+    gradient_text, leather_recipe, legacy_width, map_art, map_color, map_palette, pad_legacy,
+    particle_dust, rainbow_text, strip_legacy_codes, summon_firework, translate_legacy_codes,
+    validate_legacy_codes, wrap_legacy,
+};
+pub use mix::HueInterpolation;
+pub use scale::Scale;
+pub use space::{ColorSpace, Lab, Lch, LightnessMode, Oklab, Oklch, Xyz};
+pub use text::{Span, Style, Text};
+
 /// Represents a color with various conversion capabilities
 #[derive(Debug, Clone, PartialEq)]
 pub struct Color {
@@ -48,6 +81,14 @@ pub struct Hsv {
     pub v: f32, // 0.0 to 100.0
 }
 
+/// HWB (Hue, Whiteness, Blackness) color representation
+#[derive(Debug, Clone, PartialEq)]
+pub struct Hwb {
+    pub h: f32, // 0.0 to 360.0
+    pub w: f32, // 0.0 to 100.0
+    pub b: f32, // 0.0 to 100.0
+}
+
 /// CMYK (Cyan, Magenta, Yellow, Key/Black) color representation
 #[derive(Debug, Clone, PartialEq)]
 pub struct Cmyk {
@@ -65,8 +106,15 @@ pub enum ColorError {
     InvalidRgbValue,
     InvalidHslValue,
     InvalidHsvValue,
+    InvalidHwbValue,
     InvalidCmykValue,
     InvalidMinecraftCode,
+    InvalidMixPercentage,
+    InvalidCssColor,
+    InvalidGradient,
+    InvalidTextComponent,
+    InvalidPixelBuffer,
+    InvalidColorInt,
 }
 
 impl fmt::Display for ColorError {
@@ -77,8 +125,20 @@ impl fmt::Display for ColorError {
             ColorError::InvalidRgbValue => write!(f, "RGB values must be between 0 and 255"),
             ColorError::InvalidHslValue => write!(f, "Invalid HSL values"),
             ColorError::InvalidHsvValue => write!(f, "Invalid HSV values"),
+            ColorError::InvalidHwbValue => write!(f, "Invalid HWB values"),
             ColorError::InvalidCmykValue => write!(f, "CMYK values must be between 0 and 100"),
             ColorError::InvalidMinecraftCode => write!(f, "Invalid Minecraft color code"),
+            ColorError::InvalidMixPercentage => {
+                write!(
+                    f,
+                    "Mix percentages must be between 0 and 100 and not both zero"
+                )
+            }
+            ColorError::InvalidCssColor => write!(f, "Invalid CSS color"),
+            ColorError::InvalidGradient => write!(f, "Invalid CSS gradient"),
+            ColorError::InvalidTextComponent => write!(f, "Invalid JSON text component"),
+            ColorError::InvalidPixelBuffer => write!(f, "Pixel buffer has the wrong size"),
+            ColorError::InvalidColorInt => write!(f, "Color integer is out of range"),
         }
     }
 }
@@ -146,7 +206,7 @@ impl Color {
 
     /// Create a Color from HSL values
     pub fn from_hsl(h: f32, s: f32, l: f32) -> Result<Self, ColorError> {
-        if s < 0.0 || s > 100.0 || l < 0.0 || l > 100.0 {
+        if !(0.0..=100.0).contains(&s) || !(0.0..=100.0).contains(&l) {
             return Err(ColorError::InvalidHslValue);
         }
 
@@ -176,17 +236,25 @@ impl Color {
 
     /// Create a Color from Minecraft color code (e.g., "§c", "&4", "&#FF5733")
     pub fn from_minecraft_code(code: &str) -> Result<Self, ColorError> {
+        Color::from_minecraft_code_in(code, Edition::Java)
+    }
+
+    /// Create a Color from a Minecraft color code of the given edition
+    ///
+    /// Bedrock adds "§g" and the material codes ("§h" to "§v") but does not
+    /// support hex codes.
+    pub fn from_minecraft_code_in(code: &str, edition: Edition) -> Result<Self, ColorError> {
         if code.is_empty() {
             return Err(ColorError::InvalidMinecraftCode);
         }
 
         // Handle modern hex format: &#RRGGBB or &x&R&R&G&G&B&B
-        if code.starts_with("&#") && code.len() == 8 {
+        if edition == Edition::Java && code.starts_with("&#") && code.len() == 8 {
             return Color::from_hex(&code[2..]);
         }
 
         // Handle alternate hex format: &x&R&R&G&G&B&B
-        if code.starts_with("&x") && code.len() == 14 {
+        if edition == Edition::Java && code.starts_with("&x") && code.len() == 14 {
             let hex_chars: String = code
                 .chars()
                 .skip(2)
@@ -224,13 +292,15 @@ impl Color {
             'd' | 'D' => Ok(Color::from_rgb(255, 85, 255)),  // Light Purple
             'e' | 'E' => Ok(Color::from_rgb(255, 255, 85)),  // Yellow
             'f' | 'F' => Ok(Color::from_rgb(255, 255, 255)), // White
-            _ => Err(ColorError::InvalidMinecraftCode),
+            c => edition
+                .extra_color(c)
+                .ok_or(ColorError::InvalidMinecraftCode),
         }
     }
 
     /// Create a Color from HSV values
     pub fn from_hsv(h: f32, s: f32, v: f32) -> Result<Self, ColorError> {
-        if s < 0.0 || s > 100.0 || v < 0.0 || v > 100.0 {
+        if !(0.0..=100.0).contains(&s) || !(0.0..=100.0).contains(&v) {
             return Err(ColorError::InvalidHsvValue);
         }
 
@@ -258,17 +328,21 @@ impl Color {
         Ok(Color::from_rgb(r, g, b))
     }
 
+    /// Create a Color from HWB values
+    ///
+    /// Whiteness and blackness adding up to more than 100 produce a gray.
+    pub fn from_hwb(h: f32, w: f32, b: f32) -> Result<Self, ColorError> {
+        if !(0.0..=100.0).contains(&w) || !(0.0..=100.0).contains(&b) {
+            return Err(ColorError::InvalidHwbValue);
+        }
+
+        let [r, g, b] = space::hwb_to_srgb([h, w, b]).map(|c| (c * 255.0).round() as u8);
+        Ok(Color::from_rgb(r, g, b))
+    }
+
     /// Create a Color from CMYK values
     pub fn from_cmyk(c: f32, m: f32, y: f32, k: f32) -> Result<Self, ColorError> {
-        if c < 0.0
-            || c > 100.0
-            || m < 0.0
-            || m > 100.0
-            || y < 0.0
-            || y > 100.0
-            || k < 0.0
-            || k > 100.0
-        {
+        if [c, m, y, k].iter().any(|v| !(0.0..=100.0).contains(v)) {
             return Err(ColorError::InvalidCmykValue);
         }
 
@@ -366,6 +440,19 @@ impl Color {
         Hsv { h, s, v: v * 100.0 }
     }
 
+    /// Convert to HWB
+    pub fn to_hwb(&self) -> Hwb {
+        let r = self.r as f32 / 255.0;
+        let g = self.g as f32 / 255.0;
+        let b = self.b as f32 / 255.0;
+
+        Hwb {
+            h: self.to_hsv().h,
+            w: r.min(g.min(b)) * 100.0,
+            b: (1.0 - r.max(g.max(b))) * 100.0,
+        }
+    }
+
     /// Convert to CMYK
     pub fn to_cmyk(&self) -> Cmyk {
         let r = self.r as f32 / 255.0;
@@ -438,7 +525,12 @@ impl Color {
 
     /// Convert to Minecraft legacy color code (closest match)
     pub fn to_minecraft_code(&self) -> String {
-        let distances: Vec<(f32, char)> = vec![
+        self.to_minecraft_code_in(Edition::Java)
+    }
+
+    /// Convert to the closest color code of the given edition
+    pub fn to_minecraft_code_in(&self, edition: Edition) -> String {
+        let mut distances: Vec<(f32, char)> = vec![
             (self.color_distance(&Color::from_rgb(0, 0, 0)), '0'), // Black
             (self.color_distance(&Color::from_rgb(0, 0, 170)), '1'), // Dark Blue
             (self.color_distance(&Color::from_rgb(0, 170, 0)), '2'), // Dark Green
@@ -456,6 +548,12 @@ impl Color {
             (self.color_distance(&Color::from_rgb(255, 255, 85)), 'e'), // Yellow
             (self.color_distance(&Color::from_rgb(255, 255, 255)), 'f'), // White
         ];
+        distances.extend(
+            edition
+                .extra_colors()
+                .iter()
+                .map(|(code, color)| (self.color_distance(color), *code)),
+        );
 
         let closest = distances
             .iter()
@@ -528,6 +626,12 @@ impl fmt::Display for Hsv {
     }
 }
 
+impl fmt::Display for Hwb {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "hwb({:.1}°, {:.1}%, {:.1}%)", self.h, self.w, self.b)
+    }
+}
+
 impl fmt::Display for Cmyk {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
@@ -692,6 +796,80 @@ impl Color {
         b: 255,
         a: 1.0,
     };
+
+    // Bedrock Edition color codes
+    pub const MC_MINECOIN_GOLD: Color = Color {
+        r: 221,
+        g: 214,
+        b: 5,
+        a: 1.0,
+    };
+    pub const MC_MATERIAL_QUARTZ: Color = Color {
+        r: 227,
+        g: 212,
+        b: 209,
+        a: 1.0,
+    };
+    pub const MC_MATERIAL_IRON: Color = Color {
+        r: 206,
+        g: 202,
+        b: 202,
+        a: 1.0,
+    };
+    pub const MC_MATERIAL_NETHERITE: Color = Color {
+        r: 68,
+        g: 58,
+        b: 59,
+        a: 1.0,
+    };
+    pub const MC_MATERIAL_REDSTONE: Color = Color {
+        r: 151,
+        g: 22,
+        b: 7,
+        a: 1.0,
+    };
+    pub const MC_MATERIAL_COPPER: Color = Color {
+        r: 180,
+        g: 104,
+        b: 77,
+        a: 1.0,
+    };
+    pub const MC_MATERIAL_GOLD: Color = Color {
+        r: 222,
+        g: 177,
+        b: 45,
+        a: 1.0,
+    };
+    pub const MC_MATERIAL_EMERALD: Color = Color {
+        r: 71,
+        g: 160,
+        b: 54,
+        a: 1.0,
+    };
+    pub const MC_MATERIAL_DIAMOND: Color = Color {
+        r: 44,
+        g: 186,
+        b: 168,
+        a: 1.0,
+    };
+    pub const MC_MATERIAL_LAPIS: Color = Color {
+        r: 33,
+        g: 73,
+        b: 123,
+        a: 1.0,
+    };
+    pub const MC_MATERIAL_AMETHYST: Color = Color {
+        r: 154,
+        g: 92,
+        b: 198,
+        a: 1.0,
+    };
+    pub const MC_MATERIAL_RESIN: Color = Color {
+        r: 235,
+        g: 113,
+        b: 20,
+        a: 1.0,
+    };
 }
 
 #[cfg(test)]
@@ -735,9 +913,9 @@ mod tests {
         let blue = Color::BLUE;
         let purple = red.blend(&blue, 0.5);
 
-        assert_eq!(purple.r, 127);
+        assert_eq!(purple.r, 128);
         assert_eq!(purple.g, 0);
-        assert_eq!(purple.b, 127);
+        assert_eq!(purple.b, 128);
     }
 
     #[test]
@@ -756,6 +934,25 @@ mod tests {
         assert!(lighter.r > color.r);
     }
 
+    #[test]
+    fn test_hwb_conversion() {
+        let color = Color::from_rgb(255, 87, 51);
+        let hwb = color.to_hwb();
+        assert!((hwb.h - 10.6).abs() < 0.1);
+        assert!((hwb.w - 20.0).abs() < 0.1);
+        assert!(hwb.b.abs() < 0.1);
+        assert_eq!(Color::from_hwb(hwb.h, hwb.w, hwb.b).unwrap(), color);
+
+        assert_eq!(
+            Color::from_hwb(0.0, 60.0, 60.0).unwrap(),
+            Color::from_rgb(128, 128, 128)
+        );
+        assert_eq!(
+            Color::from_hwb(0.0, 120.0, 0.0),
+            Err(ColorError::InvalidHwbValue)
+        );
+    }
+
     #[test]
     fn test_cmyk_conversion() {
         let color = Color::from_cmyk(0.0, 100.0, 100.0, 0.0).unwrap(); // Should be red
@@ -821,4 +1018,35 @@ mod tests {
         assert_eq!(Color::MC_GOLD.g, 170);
         assert_eq!(Color::MC_GOLD.b, 0);
     }
+
+    #[test]
+    fn test_bedrock_codes() {
+        let gold = Color::from_minecraft_code_in("§g", Edition::Bedrock).unwrap();
+        assert_eq!(gold, Color::MC_MINECOIN_GOLD);
+        assert_eq!(
+            Color::from_minecraft_code_in("§G", Edition::Bedrock),
+            Ok(gold)
+        );
+        assert_eq!(
+            Color::from_minecraft_code_in("&M", Edition::Bedrock),
+            Ok(Color::MC_MATERIAL_REDSTONE)
+        );
+        let resin = Color::from_minecraft_code_in("v", Edition::Bedrock).unwrap();
+        assert_eq!(resin.to_hex(), "#EB7114");
+        assert_eq!(
+            Color::from_minecraft_code_in("&c", Edition::Bedrock).unwrap(),
+            Color::MC_RED
+        );
+
+        // Java has no material codes and Bedrock has no hex codes
+        assert!(Color::from_minecraft_code("§g").is_err());
+        assert!(Color::from_minecraft_code_in("&#FF5733", Edition::Bedrock).is_err());
+
+        let copper = Color::from_rgb(180, 100, 80);
+        assert_eq!(copper.to_minecraft_code_in(Edition::Bedrock), "§n");
+        assert_eq!(
+            copper.to_minecraft_code_in(Edition::Java),
+            copper.to_minecraft_code()
+        );
+    }
 }
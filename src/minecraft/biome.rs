@@ -0,0 +1,396 @@
+//! Biome grass, foliage and water tints
+
+use crate::{Color, ColorError};
+use std::sync::OnceLock;
+
+/// Width and height of the grass and foliage colormaps
+pub const COLORMAP_SIZE: usize = 256;
+
+/// Water color of biomes without their own
+const DEFAULT_WATER: Color = Color::from_packed(0x3F76E4);
+
+/// A grass or foliage colormap, such as `colormap/grass.png`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Colormap {
+    pixels: Vec<Color>,
+}
+
+impl Colormap {
+    /// Load a 256×256 RGBA buffer (row by row, 4 bytes per pixel)
+    pub fn from_rgba(rgba: &[u8]) -> Result<Colormap, ColorError> {
+        if rgba.len() != COLORMAP_SIZE * COLORMAP_SIZE * 4 {
+            return Err(ColorError::InvalidPixelBuffer);
+        }
+        let pixels = rgba
+            .chunks_exact(4)
+            .map(|p| Color::from_rgb(p[0], p[1], p[2]))
+            .collect();
+        Ok(Colormap { pixels })
+    }
+
+    /// The color at a temperature and downfall, as vanilla looks it up
+    ///
+    /// Both are clamped to 0.0 to 1.0, and downfall is scaled by temperature
+    /// so only the lower left triangle of the map is used.
+    pub fn sample(&self, temperature: f32, downfall: f32) -> Color {
+        let temperature = temperature.clamp(0.0, 1.0) as f64;
+        let downfall = downfall.clamp(0.0, 1.0) as f64 * temperature;
+        let x = ((1.0 - temperature) * 255.0) as usize;
+        let y = ((1.0 - downfall) * 255.0) as usize;
+        self.pixels[y * COLORMAP_SIZE + x].clone()
+    }
+}
+
+/// How a biome changes the grass color from its colormap
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum GrassModifier {
+    /// Use the colormap color
+    #[default]
+    None,
+    /// Darken the colormap color toward green
+    DarkForest,
+    /// Ignore the colormap and pick one of two colors from a noise field
+    Swamp,
+}
+
+/// The tint settings of a biome
+#[derive(Debug, Clone, PartialEq)]
+pub struct Biome {
+    pub temperature: f32,
+    pub downfall: f32,
+    pub grass_modifier: GrassModifier,
+    pub grass_color: Option<Color>, // Replaces the colormap, e.g. in badlands
+    pub foliage_color: Option<Color>, // Replaces the colormap, e.g. in swamps
+    pub water_color: Color,
+}
+
+impl Biome {
+    /// A biome tinted by the colormaps, with the default water color
+    pub fn new(temperature: f32, downfall: f32) -> Self {
+        Biome {
+            temperature,
+            downfall,
+            grass_modifier: GrassModifier::None,
+            grass_color: None,
+            foliage_color: None,
+            water_color: DEFAULT_WATER,
+        }
+    }
+
+    /// Set how the grass color is modified
+    pub fn with_grass_modifier(mut self, modifier: GrassModifier) -> Self {
+        self.grass_modifier = modifier;
+        self
+    }
+
+    /// Use a fixed grass color instead of the colormap
+    pub fn with_grass_color(mut self, color: Color) -> Self {
+        self.grass_color = Some(color);
+        self
+    }
+
+    /// Use a fixed foliage color instead of the colormap
+    pub fn with_foliage_color(mut self, color: Color) -> Self {
+        self.foliage_color = Some(color);
+        self
+    }
+
+    /// Set the water color
+    pub fn with_water_color(mut self, color: Color) -> Self {
+        self.water_color = color;
+        self
+    }
+
+    /// The tints of a vanilla overworld biome, e.g. `minecraft:swamp`
+    pub fn from_id(id: &str) -> Option<Biome> {
+        let name = id.strip_prefix("minecraft:").unwrap_or(id);
+        let color = Color::from_packed;
+        let biome = match name {
+            "plains" | "sunflower_plains" | "beach" => Biome::new(0.8, 0.4),
+            "snowy_plains" | "ice_spikes" => Biome::new(0.0, 0.5),
+            "snowy_beach" => Biome::new(0.05, 0.3).with_water_color(color(0x3D57D6)),
+            "desert" | "savanna" | "savanna_plateau" | "windswept_savanna" => Biome::new(2.0, 0.0),
+            "windswept_hills" | "windswept_gravelly_hills" | "windswept_forest" | "stony_shore" => {
+                Biome::new(0.2, 0.3)
+            }
+            "grove" => Biome::new(-0.2, 0.8),
+            "snowy_slopes" => Biome::new(-0.3, 0.9),
+            "frozen_peaks" | "jagged_peaks" => Biome::new(-0.7, 0.9),
+            "stony_peaks" => Biome::new(1.0, 0.3),
+            "lush_caves" => Biome::new(0.5, 0.5),
+            "dripstone_caves" | "deep_dark" => Biome::new(0.8, 0.4),
+            "forest" | "flower_forest" => Biome::new(0.7, 0.8),
+            "dark_forest" => Biome::new(0.7, 0.8).with_grass_modifier(GrassModifier::DarkForest),
+            "birch_forest" | "old_growth_birch_forest" => Biome::new(0.6, 0.6),
+            "taiga" => Biome::new(0.25, 0.8),
+            "old_growth_pine_taiga" => Biome::new(0.3, 0.8),
+            "old_growth_spruce_taiga" => Biome::new(0.25, 0.8),
+            "snowy_taiga" => Biome::new(-0.5, 0.4).with_water_color(color(0x3D57D6)),
+            "jungle" | "bamboo_jungle" => Biome::new(0.95, 0.9),
+            "sparse_jungle" => Biome::new(0.95, 0.8),
+            "swamp" => Biome::new(0.8, 0.9)
+                .with_grass_modifier(GrassModifier::Swamp)
+                .with_foliage_color(color(0x6A7039))
+                .with_water_color(color(0x617B64)),
+            "mangrove_swamp" => Biome::new(0.8, 0.9)
+                .with_grass_modifier(GrassModifier::Swamp)
+                .with_foliage_color(color(0x8DB127))
+                .with_water_color(color(0x3A7A6A)),
+            "badlands" | "eroded_badlands" | "wooded_badlands" => Biome::new(2.0, 0.0)
+                .with_grass_color(color(0x90814D))
+                .with_foliage_color(color(0x9E814D)),
+            "meadow" => Biome::new(0.5, 0.8).with_water_color(color(0x0E4ECF)),
+            "cherry_grove" => Biome::new(0.5, 0.8)
+                .with_grass_color(color(0xB6DB61))
+                .with_foliage_color(color(0xB6DB61))
+                .with_water_color(color(0x5DB7EF)),
+            "pale_garden" => Biome::new(0.7, 0.8)
+                .with_grass_color(color(0x778272))
+                .with_foliage_color(color(0x878D76))
+                .with_water_color(color(0x76889D)),
+            "mushroom_fields" => Biome::new(0.9, 1.0),
+            "river" | "ocean" | "deep_ocean" => Biome::new(0.5, 0.5),
+            "frozen_river" => Biome::new(0.0, 0.5).with_water_color(color(0x3938C9)),
+            "frozen_ocean" | "deep_frozen_ocean" => {
+                Biome::new(0.0, 0.5).with_water_color(color(0x3938C9))
+            }
+            "cold_ocean" | "deep_cold_ocean" => {
+                Biome::new(0.5, 0.5).with_water_color(color(0x3D57D6))
+            }
+            "lukewarm_ocean" | "deep_lukewarm_ocean" => {
+                Biome::new(0.5, 0.5).with_water_color(color(0x45ADF2))
+            }
+            "warm_ocean" => Biome::new(0.5, 0.5).with_water_color(color(0x43D5EE)),
+            _ => return None,
+        };
+        Some(biome)
+    }
+
+    /// The grass color at block `x`, `z`; only swamps depend on the position
+    pub fn grass_color(&self, grass: &Colormap, x: i32, z: i32) -> Color {
+        if self.grass_modifier == GrassModifier::Swamp {
+            let noise = biome_info_noise().value(x as f64 * 0.0225, z as f64 * 0.0225);
+            return Color::from_packed(if noise < -0.1 { 0x4C763C } else { 0x6A7039 });
+        }
+
+        let color = match &self.grass_color {
+            Some(color) => color.clone(),
+            None => grass.sample(self.temperature, self.downfall),
+        };
+        match self.grass_modifier {
+            GrassModifier::DarkForest => {
+                let rgb = color.to_minecraft_int() as u32;
+                Color::from_packed(((rgb & 0xFEFEFE) + 0x28340A) >> 1)
+            }
+            _ => color,
+        }
+    }
+
+    /// The foliage color of leaves and vines
+    pub fn foliage_color(&self, foliage: &Colormap) -> Color {
+        match &self.foliage_color {
+            Some(color) => color.clone(),
+            None => foliage.sample(self.temperature, self.downfall),
+        }
+    }
+}
+
+/// `java.util.Random`, which seeds vanilla's biome noise
+struct JavaRandom {
+    seed: u64,
+}
+
+impl JavaRandom {
+    const MULTIPLIER: u64 = 0x5DEECE66D;
+    const MASK: u64 = (1 << 48) - 1;
+
+    fn new(seed: u64) -> JavaRandom {
+        JavaRandom {
+            seed: (seed ^ Self::MULTIPLIER) & Self::MASK,
+        }
+    }
+
+    fn next(&mut self, bits: u32) -> i32 {
+        self.seed = (self.seed.wrapping_mul(Self::MULTIPLIER) + 0xB) & Self::MASK;
+        (self.seed >> (48 - bits)) as i32
+    }
+
+    fn next_int(&mut self, bound: i32) -> i32 {
+        if bound & (bound - 1) == 0 {
+            return ((bound as i64 * self.next(31) as i64) >> 31) as i32;
+        }
+        loop {
+            let bits = self.next(31);
+            let value = bits % bound;
+            if bits.wrapping_sub(value).wrapping_add(bound - 1) >= 0 {
+                return value;
+            }
+        }
+    }
+
+    fn next_double(&mut self) -> f64 {
+        let high = (self.next(26) as i64) << 27;
+        (high + self.next(27) as i64) as f64 * (1.0 / (1u64 << 53) as f64)
+    }
+}
+
+/// Vanilla's 2D simplex noise
+struct SimplexNoise {
+    permutation: [u8; 256],
+}
+
+/// Vanilla's corner gradients without z; indices are taken modulo 12
+const GRADIENTS: [[f64; 2]; 12] = [
+    [1.0, 1.0],
+    [-1.0, 1.0],
+    [1.0, -1.0],
+    [-1.0, -1.0],
+    [1.0, 0.0],
+    [-1.0, 0.0],
+    [1.0, 0.0],
+    [-1.0, 0.0],
+    [0.0, 1.0],
+    [0.0, -1.0],
+    [0.0, 1.0],
+    [0.0, -1.0],
+];
+
+impl SimplexNoise {
+    fn new(random: &mut JavaRandom) -> SimplexNoise {
+        // The offsets are drawn but unused by the biome noise
+        for _ in 0..3 {
+            random.next_double();
+        }
+        let mut permutation: [u8; 256] = std::array::from_fn(|i| i as u8);
+        for i in 0..256 {
+            let j = random.next_int(256 - i as i32) as usize;
+            permutation.swap(i, i + j);
+        }
+        SimplexNoise { permutation }
+    }
+
+    fn p(&self, i: i32) -> i32 {
+        self.permutation[(i & 255) as usize] as i32
+    }
+
+    fn corner(gradient: i32, x: f64, y: f64) -> f64 {
+        let t = 0.5 - x * x - y * y;
+        if t < 0.0 {
+            return 0.0;
+        }
+        let [gx, gy] = GRADIENTS[gradient as usize];
+        t * t * t * t * (gx * x + gy * y)
+    }
+
+    fn value(&self, x: f64, y: f64) -> f64 {
+        let sqrt3 = 3f64.sqrt();
+        let f2 = 0.5 * (sqrt3 - 1.0);
+        let g2 = (3.0 - sqrt3) / 6.0;
+
+        let skew = (x + y) * f2;
+        let i = (x + skew).floor() as i32;
+        let j = (y + skew).floor() as i32;
+        let unskew = (i + j) as f64 * g2;
+        let (x0, y0) = (x - (i as f64 - unskew), y - (j as f64 - unskew));
+        let (i1, j1) = if x0 > y0 { (1, 0) } else { (0, 1) };
+        let (x1, y1) = (x0 - i1 as f64 + g2, y0 - j1 as f64 + g2);
+        let (x2, y2) = (x0 - 1.0 + 2.0 * g2, y0 - 1.0 + 2.0 * g2);
+
+        let (ii, jj) = (i & 255, j & 255);
+        let g0 = self.p(ii + self.p(jj)) % 12;
+        let g1 = self.p(ii + i1 + self.p(jj + j1)) % 12;
+        let g2i = self.p(ii + 1 + self.p(jj + 1)) % 12;
+
+        70.0 * (Self::corner(g0, x0, y0) + Self::corner(g1, x1, y1) + Self::corner(g2i, x2, y2))
+    }
+}
+
+/// The noise that picks swamp grass colors, seeded with 2345 as in vanilla
+fn biome_info_noise() -> &'static SimplexNoise {
+    static NOISE: OnceLock<SimplexNoise> = OnceLock::new();
+    NOISE.get_or_init(|| SimplexNoise::new(&mut JavaRandom::new(2345)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A colormap whose red and green channels are the lookup coordinates
+    fn coordinate_colormap() -> Colormap {
+        let rgba: Vec<u8> = (0..COLORMAP_SIZE * COLORMAP_SIZE)
+            .flat_map(|i| [(i % 256) as u8, (i / 256) as u8, 0, 255])
+            .collect();
+        Colormap::from_rgba(&rgba).unwrap()
+    }
+
+    #[test]
+    fn test_java_random() {
+        let mut random = JavaRandom::new(42);
+        assert_eq!(random.next(32), -1170105035);
+        let mut random = JavaRandom::new(42);
+        assert_eq!(random.next_double(), 0.7275636800328681);
+        assert_eq!(JavaRandom::new(42).next_int(10), 0);
+    }
+
+    #[test]
+    fn test_colormap() {
+        let colormap = coordinate_colormap();
+        // Plains: 0.8 as f32 is just above 0.8, so x truncates to 50
+        assert_eq!(colormap.sample(0.8, 0.4), Color::from_rgb(50, 173, 0));
+        assert_eq!(colormap.sample(-0.5, 2.0), Color::from_rgb(255, 255, 0));
+        assert_eq!(
+            Colormap::from_rgba(&[0; 4]),
+            Err(ColorError::InvalidPixelBuffer)
+        );
+    }
+
+    #[test]
+    fn test_biome_tints() {
+        let colormap = coordinate_colormap();
+        let plains = Biome::from_id("minecraft:plains").unwrap();
+        assert_eq!(
+            plains.grass_color(&colormap, 0, 0),
+            Color::from_rgb(50, 173, 0)
+        );
+        assert_eq!(plains.water_color.to_hex(), "#3F76E4");
+
+        // The colormap gives (76, 112, 0), then ((c & 0xFEFEFE) + 0x28340A) >> 1
+        let dark_forest = Biome::from_id("dark_forest").unwrap();
+        assert_eq!(
+            dark_forest.grass_color(&colormap, 0, 0),
+            Color::from_rgb(58, 82, 5)
+        );
+
+        let badlands = Biome::from_id("eroded_badlands").unwrap();
+        assert_eq!(badlands.grass_color(&colormap, 0, 0).to_hex(), "#90814D");
+        assert_eq!(badlands.foliage_color(&colormap).to_hex(), "#9E814D");
+        // Temperatures below zero clamp to the colormap's corner
+        let peaks = Biome::from_id("frozen_peaks").unwrap();
+        assert_eq!(
+            peaks.grass_color(&colormap, 0, 0),
+            Color::from_rgb(255, 255, 0)
+        );
+        let pale_garden = Biome::from_id("pale_garden").unwrap();
+        assert_eq!(pale_garden.foliage_color(&colormap).to_hex(), "#878D76");
+        assert_eq!(pale_garden.water_color.to_hex(), "#76889D");
+        assert!(Biome::from_id("windswept_gravelly_hills").is_some());
+        assert_eq!(Biome::from_id("the_end"), None);
+    }
+
+    #[test]
+    fn test_swamp_noise() {
+        let colormap = coordinate_colormap();
+        let swamp = Biome::from_id("swamp").unwrap();
+        let colors: Vec<Color> = (0..64)
+            .map(|i| swamp.grass_color(&colormap, i * 16, i * 8))
+            .collect();
+        assert!(colors.contains(&Color::from_packed(0x4C763C)));
+        assert!(colors.contains(&Color::from_packed(0x6A7039)));
+
+        let noise = biome_info_noise();
+        for i in 0..1000 {
+            let value = noise.value(i as f64 * 0.37, i as f64 * -0.11);
+            assert!((-1.0..=1.0).contains(&value));
+        }
+    }
+}
@@ -0,0 +1,219 @@
+//! Colors as command and NBT values, and ready-to-paste commands
+
+use crate::{Color, ColorError};
+
+/// The data version of 1.20.5, which replaced item NBT with components
+pub const DATA_VERSION_1_20_5: i32 = 3837;
+
+/// The data version of 1.21.4, the default for written map files
+pub const DATA_VERSION_1_21_4: i32 = 4189;
+
+/// The data version of 1.21.5, which simplified `dyed_color` to an integer
+pub const DATA_VERSION_1_21_5: i32 = 4325;
+
+impl Color {
+    /// Convert to the decimal RGB integer used by NBT, e.g. `display.color`
+    pub fn to_minecraft_int(&self) -> i32 {
+        (self.r as i32) << 16 | (self.g as i32) << 8 | self.b as i32
+    }
+
+    /// Convert to an ARGB integer as a signed int, as NBT stores it
+    ///
+    /// Colors with alpha of 50% or more are negative.
+    pub fn to_minecraft_argb_int(&self) -> i32 {
+        self.to_minecraft_argb_uint() as i32
+    }
+
+    /// Convert to an ARGB integer as an unsigned int, as commands print it
+    pub fn to_minecraft_argb_uint(&self) -> u32 {
+        let alpha = (self.a * 255.0).round() as u32;
+        alpha << 24 | self.to_minecraft_int() as u32
+    }
+
+    /// Create a Color from a decimal integer, signed or unsigned
+    ///
+    /// Values with a zero top byte are plain RGB and opaque; otherwise the
+    /// top byte is alpha. Values outside both the `i32` and `u32` ranges are
+    /// rejected.
+    pub fn from_minecraft_int(value: i64) -> Result<Color, ColorError> {
+        if value < i32::MIN as i64 || value > u32::MAX as i64 {
+            return Err(ColorError::InvalidColorInt);
+        }
+        let bits = value as u32;
+        let [alpha, r, g, b] = bits.to_be_bytes();
+        let a = if alpha == 0 {
+            1.0
+        } else {
+            alpha as f32 / 255.0
+        };
+        Ok(Color::from_rgba(r, g, b, a))
+    }
+
+    /// Convert to particle color floats (0.0 to 1.0), e.g. for `dust`
+    pub fn to_particle_floats(&self) -> [f32; 3] {
+        [self.r, self.g, self.b].map(|c| c as f32 / 255.0)
+    }
+
+    /// Create a Color from particle color floats (0.0 to 1.0)
+    pub fn from_particle_floats(rgb: [f32; 3]) -> Result<Color, ColorError> {
+        if rgb.iter().any(|c| !(0.0..=1.0).contains(c)) {
+            return Err(ColorError::InvalidRgbValue);
+        }
+        let [r, g, b] = rgb.map(|c| (c * 255.0).round() as u8);
+        Ok(Color::from_rgb(r, g, b))
+    }
+}
+
+/// A float as commands write it: up to 3 decimals, with at least one
+fn float(value: f32) -> String {
+    let text = format!("{:.3}", value);
+    let text = text.trim_end_matches('0');
+    if text.ends_with('.') {
+        format!("{}0", text)
+    } else {
+        text.to_string()
+    }
+}
+
+/// `/give` for a dyed leather item such as `leather_chestplate`
+pub fn give_dyed_item(item: &str, color: &Color, data_version: i32) -> String {
+    let rgb = color.to_minecraft_int();
+    if data_version >= DATA_VERSION_1_21_5 {
+        format!("/give @p minecraft:{}[minecraft:dyed_color={}]", item, rgb)
+    } else if data_version >= DATA_VERSION_1_20_5 {
+        format!(
+            "/give @p minecraft:{}[minecraft:dyed_color={{rgb:{}}}]",
+            item, rgb
+        )
+    } else {
+        format!("/give @p minecraft:{}{{display:{{color:{}}}}}", item, rgb)
+    }
+}
+
+/// `/give` for a potion with a custom color
+pub fn give_potion(color: &Color, data_version: i32) -> String {
+    let rgb = color.to_minecraft_int();
+    if data_version >= DATA_VERSION_1_20_5 {
+        format!(
+            "/give @p minecraft:potion[minecraft:potion_contents={{custom_color:{}}}]",
+            rgb
+        )
+    } else {
+        format!("/give @p minecraft:potion{{CustomPotionColor:{}}}", rgb)
+    }
+}
+
+/// `/particle` for a `dust` particle at the executing position
+pub fn particle_dust(color: &Color, scale: f32, data_version: i32) -> String {
+    let [r, g, b] = color.to_particle_floats().map(float);
+    if data_version >= DATA_VERSION_1_20_5 {
+        format!(
+            "/particle minecraft:dust{{color:[{},{},{}],scale:{}}} ~ ~ ~",
+            r,
+            g,
+            b,
+            float(scale)
+        )
+    } else {
+        format!(
+            "/particle minecraft:dust {} {} {} {} ~ ~ ~",
+            r,
+            g,
+            b,
+            float(scale)
+        )
+    }
+}
+
+/// `/summon` for a firework rocket that bursts into a small ball of `colors`
+pub fn summon_firework(colors: &[Color], data_version: i32) -> String {
+    let colors: Vec<String> = colors
+        .iter()
+        .map(|color| color.to_minecraft_int().to_string())
+        .collect();
+    let colors = colors.join(",");
+    let item = if data_version >= DATA_VERSION_1_20_5 {
+        format!(
+            "id:\"minecraft:firework_rocket\",count:1,components:{{\"minecraft:fireworks\":\
+             {{explosions:[{{shape:\"small_ball\",colors:[I;{}]}}]}}}}",
+            colors
+        )
+    } else {
+        format!(
+            "id:\"minecraft:firework_rocket\",Count:1b,tag:{{Fireworks:\
+             {{Explosions:[{{Type:0,Colors:[I;{}]}}]}}}}",
+            colors
+        )
+    };
+    format!(
+        "/summon minecraft:firework_rocket ~ ~ ~ {{LifeTime:30,FireworksItem:{{{}}}}}",
+        item
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_color_ints() {
+        let color = Color::from_rgb(255, 87, 51);
+        assert_eq!(color.to_minecraft_int(), 16734003);
+        assert_eq!(color.to_minecraft_argb_uint(), 4294924083);
+        assert_eq!(color.to_minecraft_argb_int(), -43213);
+
+        assert_eq!(Color::from_minecraft_int(16734003), Ok(color.clone()));
+        assert_eq!(Color::from_minecraft_int(-43213), Ok(color.clone()));
+        assert_eq!(Color::from_minecraft_int(4294924083), Ok(color));
+        let translucent = Color::from_minecraft_int(0x80FF0000).unwrap();
+        assert!((translucent.a - 128.0 / 255.0).abs() < 1e-6);
+        assert_eq!(
+            Color::from_minecraft_int(1 << 32),
+            Err(ColorError::InvalidColorInt)
+        );
+    }
+
+    #[test]
+    fn test_particle_floats() {
+        let color = Color::from_rgb(255, 128, 0);
+        assert_eq!(color.to_particle_floats(), [1.0, 128.0 / 255.0, 0.0]);
+        assert_eq!(Color::from_particle_floats([1.0, 0.502, 0.0]), Ok(color));
+        assert_eq!(
+            Color::from_particle_floats([1.5, 0.0, 0.0]),
+            Err(ColorError::InvalidRgbValue)
+        );
+    }
+
+    #[test]
+    fn test_snippets() {
+        let color = Color::from_rgb(255, 128, 0);
+        assert_eq!(
+            give_dyed_item("leather_helmet", &color, 3700),
+            "/give @p minecraft:leather_helmet{display:{color:16744448}}"
+        );
+        assert_eq!(
+            give_dyed_item("leather_helmet", &color, DATA_VERSION_1_20_5),
+            "/give @p minecraft:leather_helmet[minecraft:dyed_color={rgb:16744448}]"
+        );
+        assert_eq!(
+            give_dyed_item("leather_helmet", &color, DATA_VERSION_1_21_5),
+            "/give @p minecraft:leather_helmet[minecraft:dyed_color=16744448]"
+        );
+        assert_eq!(
+            give_potion(&color, 3700),
+            "/give @p minecraft:potion{CustomPotionColor:16744448}"
+        );
+        assert_eq!(
+            particle_dust(&color, 1.5, DATA_VERSION_1_21_4),
+            "/particle minecraft:dust{color:[1.0,0.502,0.0],scale:1.5} ~ ~ ~"
+        );
+        assert_eq!(
+            particle_dust(&color, 1.0, 3700),
+            "/particle minecraft:dust 1.0 0.502 0.0 1.0 ~ ~ ~"
+        );
+        assert!(
+            summon_firework(&[color, Color::WHITE], DATA_VERSION_1_21_4)
+                .contains("explosions:[{shape:\"small_ball\",colors:[I;16744448,16777215]}]")
+        );
+    }
+}
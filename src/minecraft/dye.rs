@@ -0,0 +1,271 @@
+//! The 16 dye colors and the blocks dyed with them
+
+use super::map::BASE_COLORS;
+use crate::Color;
+
+/// One of the 16 Minecraft dye colors, in vanilla ID order
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DyeColor {
+    White,
+    Orange,
+    Magenta,
+    LightBlue,
+    Yellow,
+    Lime,
+    Pink,
+    Gray,
+    LightGray,
+    Cyan,
+    Purple,
+    Blue,
+    Brown,
+    Green,
+    Red,
+    Black,
+}
+
+/// An opaque block that comes in all 16 dye colors
+///
+/// Stained glass is left out: it is translucent, so how it looks depends on
+/// what is behind it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DyedBlock {
+    Wool,
+    Concrete,
+    Terracotta,
+    GlazedTerracotta,
+}
+
+/// Names, in ID order
+const NAMES: [&str; 16] = [
+    "white",
+    "orange",
+    "magenta",
+    "light_blue",
+    "yellow",
+    "lime",
+    "pink",
+    "gray",
+    "light_gray",
+    "cyan",
+    "purple",
+    "blue",
+    "brown",
+    "green",
+    "red",
+    "black",
+];
+
+/// Vanilla's `textureDiffuseColor`, used for leather, sheep and beds
+const TEXTURE_COLORS: [u32; 16] = [
+    0xF9FFFE, 0xF9801D, 0xC74EBD, 0x3AB3DA, 0xFED83D, 0x80C71F, 0xF38BAA, 0x474F52, 0x9D9D97,
+    0x169C9C, 0x8932B8, 0x3C44AA, 0x835432, 0x5E7C16, 0xB02E26, 0x1D1D21,
+];
+
+/// Firework star colors
+const FIREWORK_COLORS: [u32; 16] = [
+    0xF0F0F0, 0xEB8844, 0xC354CD, 0x6689D3, 0xDECF2A, 0x41CD34, 0xD88198, 0x434343, 0xABABAB,
+    0x287697, 0x7B2FBE, 0x253192, 0x51301A, 0x3B511A, 0xB3312C, 0x1E1B1B,
+];
+
+/// Colors of dyed sign text
+const TEXT_COLORS: [u32; 16] = [
+    0xFFFFFF, 0xFF681F, 0xFF00FF, 0x9AC0CD, 0xFFFF00, 0xBFFF00, 0xFF69B4, 0x808080, 0xD3D3D3,
+    0x00FFFF, 0xA020F0, 0x0000FF, 0x8B4513, 0x00FF00, 0xFF0000, 0x000000,
+];
+
+/// Base map color IDs of blocks in each dye color: snow for white, then the
+/// 15 color entries in order
+const MAP_BASE_IDS: [usize; 16] = [
+    8, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29,
+];
+
+/// Base map colors of blocks in each dye color
+const MAP_COLORS: [u32; 16] = {
+    let mut colors = [0; 16];
+    let mut i = 0;
+    while i < 16 {
+        colors[i] = BASE_COLORS[MAP_BASE_IDS[i]];
+        i += 1;
+    }
+    colors
+};
+
+/// Average colors of each block texture, in dye color order
+const WOOL_COLORS: [u32; 16] = [
+    0xE9ECEC, 0xF07613, 0xBD44B3, 0x3AAFD9, 0xF8C527, 0x70B919, 0xED8DAC, 0x3E4447, 0x8E8E86,
+    0x158991, 0x792AAC, 0x35399D, 0x724728, 0x546D1B, 0xA02722, 0x141519,
+];
+const CONCRETE_COLORS: [u32; 16] = [
+    0xCFD5D6, 0xE06100, 0xA9309F, 0x2389C6, 0xF0AF15, 0x5EA818, 0xD5658E, 0x36393D, 0x7D7D73,
+    0x157788, 0x641F9C, 0x2C2E8F, 0x603B1F, 0x495B24, 0x8E2020, 0x080A0F,
+];
+const TERRACOTTA_COLORS: [u32; 16] = [
+    0xD1B2A1, 0xA15325, 0x95586C, 0x716C89, 0xBA8523, 0x677534, 0xA14E4E, 0x392A23, 0x876A61,
+    0x565B5B, 0x764656, 0x4A3B5B, 0x4D3323, 0x4C532A, 0x8F3D2E, 0x251610,
+];
+const GLAZED_TERRACOTTA_COLORS: [u32; 16] = [
+    0xBCD4CA, 0x9A935B, 0xD064BF, 0x5EA4D0, 0xEAC058, 0xA2C537, 0xEB9AB5, 0x535A5D, 0x90A6A7,
+    0x34767D, 0x6D3098, 0x2F408B, 0x776A55, 0x758E43, 0xB53B35, 0x431E20,
+];
+
+impl DyeColor {
+    /// All dye colors, in ID order
+    pub const ALL: [DyeColor; 16] = [
+        DyeColor::White,
+        DyeColor::Orange,
+        DyeColor::Magenta,
+        DyeColor::LightBlue,
+        DyeColor::Yellow,
+        DyeColor::Lime,
+        DyeColor::Pink,
+        DyeColor::Gray,
+        DyeColor::LightGray,
+        DyeColor::Cyan,
+        DyeColor::Purple,
+        DyeColor::Blue,
+        DyeColor::Brown,
+        DyeColor::Green,
+        DyeColor::Red,
+        DyeColor::Black,
+    ];
+
+    /// The vanilla numeric ID (0 for white to 15 for black)
+    pub fn id(self) -> u8 {
+        self as u8
+    }
+
+    /// The dye color with a numeric ID
+    pub fn from_id(id: u8) -> Option<DyeColor> {
+        DyeColor::ALL.get(id as usize).copied()
+    }
+
+    /// The name used in IDs, e.g. `light_blue`
+    pub fn name(self) -> &'static str {
+        NAMES[self as usize]
+    }
+
+    /// The dye color with a name such as `light_blue`
+    pub fn from_name(name: &str) -> Option<DyeColor> {
+        let index = NAMES.iter().position(|n| *n == name)?;
+        Some(DyeColor::ALL[index])
+    }
+
+    /// The color used to tint leather armor, sheep, beds and banners
+    pub fn texture_color(self) -> Color {
+        Color::from_packed(TEXTURE_COLORS[self as usize])
+    }
+
+    /// The color of a firework star made with this dye
+    pub fn firework_color(self) -> Color {
+        Color::from_packed(FIREWORK_COLORS[self as usize])
+    }
+
+    /// The color of sign text dyed with this dye
+    pub fn text_color(self) -> Color {
+        Color::from_packed(TEXT_COLORS[self as usize])
+    }
+
+    /// The base map color of blocks in this color
+    pub fn map_color(self) -> Color {
+        Color::from_packed(MAP_COLORS[self as usize])
+    }
+
+    /// The average texture color of a block in this color
+    pub fn block_color(self, block: DyedBlock) -> Color {
+        Color::from_packed(block.colors()[self as usize])
+    }
+
+    /// The namespaced block ID, e.g. `minecraft:light_blue_wool`
+    pub fn block_id(self, block: DyedBlock) -> String {
+        format!("minecraft:{}_{}", self.name(), block.suffix())
+    }
+}
+
+impl DyedBlock {
+    /// All dyed block kinds
+    pub const ALL: [DyedBlock; 4] = [
+        DyedBlock::Wool,
+        DyedBlock::Concrete,
+        DyedBlock::Terracotta,
+        DyedBlock::GlazedTerracotta,
+    ];
+
+    fn colors(self) -> &'static [u32; 16] {
+        match self {
+            DyedBlock::Wool => &WOOL_COLORS,
+            DyedBlock::Concrete => &CONCRETE_COLORS,
+            DyedBlock::Terracotta => &TERRACOTTA_COLORS,
+            DyedBlock::GlazedTerracotta => &GLAZED_TERRACOTTA_COLORS,
+        }
+    }
+
+    /// The block ID after the color name, e.g. `glazed_terracotta`
+    fn suffix(self) -> &'static str {
+        match self {
+            DyedBlock::Wool => "wool",
+            DyedBlock::Concrete => "concrete",
+            DyedBlock::Terracotta => "terracotta",
+            DyedBlock::GlazedTerracotta => "glazed_terracotta",
+        }
+    }
+}
+
+impl Color {
+    /// The dye color whose block looks closest, by Oklab distance
+    pub fn nearest_dye(&self, block: DyedBlock) -> DyeColor {
+        self.nearest_block(&[block]).unwrap().1
+    }
+
+    /// The closest block among several kinds, by Oklab distance
+    ///
+    /// Returns `None` if `blocks` is empty.
+    pub fn nearest_block(&self, blocks: &[DyedBlock]) -> Option<(DyedBlock, DyeColor)> {
+        blocks
+            .iter()
+            .flat_map(|&block| DyeColor::ALL.map(|dye| (block, dye)))
+            .map(|(block, dye)| {
+                let distance = self.oklab_distance(&dye.block_color(block));
+                (distance, block, dye)
+            })
+            .min_by(|a, b| a.0.partial_cmp(&b.0).unwrap())
+            .map(|(_, block, dye)| (block, dye))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dye_colors() {
+        assert_eq!(DyeColor::from_id(3), Some(DyeColor::LightBlue));
+        assert_eq!(DyeColor::from_name("light_gray"), Some(DyeColor::LightGray));
+        assert_eq!(DyeColor::Black.id(), 15);
+        assert_eq!(DyeColor::from_id(16), None);
+
+        assert_eq!(DyeColor::Red.texture_color().to_hex(), "#B02E26");
+        assert_eq!(DyeColor::Blue.firework_color().to_hex(), "#253192");
+        assert_eq!(DyeColor::Brown.text_color().to_hex(), "#8B4513");
+        assert_eq!(DyeColor::Cyan.map_color().to_hex(), "#4C7F99");
+        assert_eq!(
+            DyeColor::LightBlue.block_id(DyedBlock::GlazedTerracotta),
+            "minecraft:light_blue_glazed_terracotta"
+        );
+    }
+
+    #[test]
+    fn test_nearest_block() {
+        let orange = Color::from_rgb(230, 100, 10);
+        assert_eq!(orange.nearest_dye(DyedBlock::Concrete), DyeColor::Orange);
+        assert_eq!(
+            Color::from_rgb(150, 90, 65).nearest_dye(DyedBlock::Terracotta),
+            DyeColor::Orange
+        );
+        assert_eq!(
+            Color::from_rgb(10, 10, 14).nearest_block(&DyedBlock::ALL),
+            Some((DyedBlock::Concrete, DyeColor::Black))
+        );
+        assert_eq!(Color::WHITE.nearest_block(&[]), None);
+    }
+}
@@ -0,0 +1,149 @@
+//! Per-character gradient and rainbow chat text
+
+use super::legacy::{LegacyToken, legacy_tokens};
+use super::{Edition, HexFormat};
+use crate::{Color, Gradient};
+
+/// Mark each `§` followed by an unknown code, such as `§z`, and the character
+/// after it; vanilla hides these, but shows `&` as ordinary text
+fn unknown_codes(tokens: &[LegacyToken]) -> Vec<bool> {
+    let mut unknown = vec![false; tokens.len()];
+    for i in 1..tokens.len() {
+        if let (LegacyToken::Char('§'), LegacyToken::Char(c)) = (&tokens[i - 1], &tokens[i])
+            && !c.is_whitespace()
+            && !unknown[i - 1]
+        {
+            unknown[i - 1] = true;
+            unknown[i] = true;
+        }
+    }
+    unknown
+}
+
+/// Color each visible character of `text` with the next color from `colors`
+fn colorize(text: &str, format: HexFormat, colors: impl Fn(usize) -> Vec<Color>) -> String {
+    let tokens = legacy_tokens(text, &['&', '§'], Edition::Java);
+    let unknown = unknown_codes(&tokens);
+    let visible = tokens
+        .iter()
+        .zip(&unknown)
+        .filter(|&(t, &unknown)| {
+            !unknown && matches!(t, LegacyToken::Char(c) if !c.is_whitespace())
+        })
+        .count();
+    let mut colors = colors(visible).into_iter();
+
+    let mut result = String::new();
+    let mut formats = String::new();
+    for (token, unknown) in tokens.into_iter().zip(unknown) {
+        match token {
+            // Unknown `§` codes are kept as written, without using up a color
+            LegacyToken::Char(c) if c.is_whitespace() || unknown => result.push(c),
+            LegacyToken::Char(c) => {
+                if let Some(color) = colors.next() {
+                    result.push_str(&format.code(&color));
+                }
+                // A color code clears formatting, so re-apply it after every one
+                result.push_str(&formats);
+                result.push(c);
+            }
+            LegacyToken::Format(code, _) => {
+                if !formats.contains(code) {
+                    formats.push(format.prefix());
+                    formats.push(code);
+                }
+            }
+            // Color codes are replaced by the gradient and, as in vanilla,
+            // reset the active formatting
+            LegacyToken::Color(..) | LegacyToken::Reset(_) => formats.clear(),
+        }
+    }
+
+    result
+}
+
+/// Color `text` character by character along `gradient`
+///
+/// Whitespace is left uncolored and does not use up gradient positions.
+/// Formatting codes (`&l`, `§o`, ...) are kept and re-applied after each
+/// color code; existing color codes are replaced by the gradient. Unknown
+/// `§` codes such as `§z` are left as written and uncolored, while `&` before
+/// an unknown code is colored like any other character.
+pub fn gradient_text(text: &str, gradient: &Gradient, format: HexFormat) -> String {
+    colorize(text, format, |n| gradient.take(n))
+}
+
+/// Color `text` with hues cycling once around the color wheel
+///
+/// `phase` offsets the starting hue in degrees, e.g. to animate the text.
+pub fn rainbow_text(text: &str, format: HexFormat, phase: f32) -> String {
+    colorize(text, format, |n| {
+        (0..n)
+            .map(|i| {
+                let hue = (phase + 360.0 * i as f32 / n as f32).rem_euclid(360.0);
+                Color::from_hsv(hue, 100.0, 100.0).unwrap()
+            })
+            .collect()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ColorSpace;
+
+    #[test]
+    fn test_gradient_text() {
+        let gradient =
+            Gradient::from_colors(&[Color::RED, Color::BLUE]).with_space(ColorSpace::Srgb);
+        assert_eq!(
+            gradient_text("A b", &gradient, HexFormat::Ampersand),
+            "&#FF0000A &#0000FFb"
+        );
+        assert_eq!(
+            gradient_text("Hi", &gradient, HexFormat::SectionX),
+            "§x§F§F§0§0§0§0H§x§0§0§0§0§F§Fi"
+        );
+        assert_eq!(
+            gradient_text("!", &gradient, HexFormat::AmpersandX),
+            "&x&F&F&0&0&0&0!"
+        );
+    }
+
+    #[test]
+    fn test_formatting_codes_preserved() {
+        let gradient =
+            Gradient::from_colors(&[Color::RED, Color::BLUE]).with_space(ColorSpace::Srgb);
+        assert_eq!(
+            gradient_text("&lA§cB", &gradient, HexFormat::Ampersand),
+            "&#FF0000&lA&#0000FFB"
+        );
+        assert_eq!(
+            gradient_text("&#123456&o&lA&rB", &gradient, HexFormat::Ampersand),
+            "&#FF0000&o&lA&#0000FFB"
+        );
+        // Unknown `§` codes are passed through and take no gradient position
+        assert_eq!(
+            gradient_text("A§zB", &gradient, HexFormat::Ampersand),
+            "&#FF0000A§z&#0000FFB"
+        );
+        // An `&` inside a word is ordinary text
+        assert_eq!(
+            gradient_text("o&J", &gradient, HexFormat::Ampersand),
+            "&#FF0000o&#800080&&#0000FFJ"
+        );
+        assert_eq!(
+            gradient_text("A & B", &gradient, HexFormat::Ampersand),
+            "&#FF0000A &#800080& &#0000FFB"
+        );
+    }
+
+    #[test]
+    fn test_rainbow_text() {
+        assert_eq!(
+            rainbow_text("abc", HexFormat::Ampersand, 0.0),
+            "&#FF0000a&#00FF00b&#0000FFc"
+        );
+        assert_eq!(rainbow_text("a", HexFormat::Ampersand, -120.0), "&#0000FFa");
+    }
+}
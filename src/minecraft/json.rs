@@ -0,0 +1,197 @@
+//! JSON text components, as used by `tellraw` and server list pings
+
+use super::{color_name, named_color};
+use crate::json::Json;
+use crate::{Color, ColorError, Span, Style, Text};
+
+/// The style of a component: its parent's style with its own fields applied
+fn component_style(json: &Json, parent: &Style) -> Result<Style, ColorError> {
+    let mut style = parent.clone();
+    let Json::Object(members) = json else {
+        return Ok(style);
+    };
+
+    for (key, value) in members {
+        let flag = match key.as_str() {
+            "bold" => &mut style.bold,
+            "italic" => &mut style.italic,
+            "underlined" => &mut style.underlined,
+            "strikethrough" => &mut style.strikethrough,
+            "obfuscated" => &mut style.obfuscated,
+            "color" => {
+                let Json::String(name) = value else {
+                    return Err(ColorError::InvalidTextComponent);
+                };
+                style.color = match name.as_str() {
+                    "reset" => None,
+                    hex if hex.starts_with('#') => {
+                        Some(Color::from_hex(hex).map_err(|_| ColorError::InvalidTextComponent)?)
+                    }
+                    name => Some(named_color(name).ok_or(ColorError::InvalidTextComponent)?),
+                };
+                continue;
+            }
+            _ => continue,
+        };
+        match value {
+            Json::Bool(enabled) => *flag = *enabled,
+            _ => return Err(ColorError::InvalidTextComponent),
+        }
+    }
+
+    Ok(style)
+}
+
+/// The literal content of a component object
+fn component_text(json: &Json) -> Result<String, ColorError> {
+    // Translatable and keybind components fall back to their key
+    let content = ["text", "translate", "keybind"]
+        .iter()
+        .find_map(|key| json.get(key));
+    match content {
+        None => Ok(String::new()),
+        Some(Json::String(text)) => Ok(text.clone()),
+        Some(Json::Number(n)) => Ok(n.to_string()),
+        Some(Json::Bool(b)) => Ok(b.to_string()),
+        Some(_) => Err(ColorError::InvalidTextComponent),
+    }
+}
+
+fn read_component(json: &Json, parent: &Style, text: &mut Text) -> Result<(), ColorError> {
+    match json {
+        Json::String(s) => text.push(s, parent),
+        Json::Number(n) => text.push(&n.to_string(), parent),
+        Json::Bool(b) => text.push(&b.to_string(), parent),
+        // The first element of a list is the parent of the others
+        Json::Array(items) => {
+            let (first, rest) = items
+                .split_first()
+                .ok_or(ColorError::InvalidTextComponent)?;
+            read_component(first, parent, text)?;
+            let style = component_style(first, parent)?;
+            for item in rest {
+                read_component(item, &style, text)?;
+            }
+        }
+        Json::Object(_) => {
+            let style = component_style(json, parent)?;
+            text.push(&component_text(json)?, &style);
+            match json.get("extra") {
+                None => {}
+                Some(Json::Array(children)) => {
+                    for child in children {
+                        read_component(child, &style, text)?;
+                    }
+                }
+                Some(_) => return Err(ColorError::InvalidTextComponent),
+            }
+        }
+        Json::Null => return Err(ColorError::InvalidTextComponent),
+    }
+    Ok(())
+}
+
+/// Read an already parsed text component
+pub(super) fn text_from_value(json: &Json) -> Result<Text, ColorError> {
+    let mut text = Text::new();
+    read_component(json, &Style::default(), &mut text)?;
+    Ok(text)
+}
+
+fn span_component(span: &Span) -> Json {
+    let style = &span.style;
+    let mut members = vec![("text".to_string(), Json::String(span.text.clone()))];
+
+    if let Some(color) = &style.color {
+        let name = color_name(color).map_or_else(|| color.to_hex(), str::to_string);
+        members.push(("color".to_string(), Json::String(name)));
+    }
+    let flags = [
+        ("bold", style.bold),
+        ("italic", style.italic),
+        ("underlined", style.underlined),
+        ("strikethrough", style.strikethrough),
+        ("obfuscated", style.obfuscated),
+    ];
+    for (key, enabled) in flags {
+        if enabled {
+            members.push((key.to_string(), Json::Bool(true)));
+        }
+    }
+
+    Json::Object(members)
+}
+
+impl Text {
+    /// Parse a JSON text component
+    ///
+    /// Accepts plain strings, objects with `extra` children and lists whose
+    /// first element is the parent of the rest. Colors may be one of the 16
+    /// named colors or `#RRGGBB`.
+    pub fn from_json(json: &str) -> Result<Text, ColorError> {
+        let json = Json::parse(json).ok_or(ColorError::InvalidTextComponent)?;
+        text_from_value(&json)
+    }
+
+    /// Serialize to a JSON text component
+    ///
+    /// The 16 legacy colors are written by name and others as `#RRGGBB`;
+    /// call [`Text::downsampled`] first for clients before 1.16.
+    pub fn to_json(&self) -> String {
+        let component = match self.spans.as_slice() {
+            [] => Json::Object(vec![("text".to_string(), Json::String(String::new()))]),
+            [span] => span_component(span),
+            spans => Json::Object(vec![
+                ("text".to_string(), Json::String(String::new())),
+                (
+                    "extra".to_string(),
+                    Json::Array(spans.iter().map(span_component).collect()),
+                ),
+            ]),
+        };
+        component.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::HexFormat;
+
+    #[test]
+    fn test_from_json() {
+        let json = r##"{"text":"Hi ","color":"gold","bold":true,"extra":[
+            {"text":"there","color":"#FF5733","bold":false},
+            ["", {"text":"!","italic":true}, "?"]
+        ]}"##;
+        let text = Text::from_json(json).unwrap();
+        assert_eq!(
+            text.to_legacy(HexFormat::Ampersand),
+            "&6&lHi &#FF5733there&6&l&o!&6&l?"
+        );
+
+        assert_eq!(Text::from_json("\"plain\"").unwrap().plain(), "plain");
+        assert_eq!(
+            Text::from_json(r#"{"text":"x","color":"pink"}"#),
+            Err(ColorError::InvalidTextComponent)
+        );
+        assert_eq!(Text::from_json("[]"), Err(ColorError::InvalidTextComponent));
+    }
+
+    #[test]
+    fn test_to_json() {
+        let text = Text::from_legacy("§c§lHello &#FF5733world");
+        let json = text.to_json();
+        assert_eq!(
+            json,
+            r##"{"text":"","extra":[{"text":"Hello ","color":"red","bold":true},{"text":"world","color":"#FF5733"}]}"##
+        );
+        assert_eq!(Text::from_json(&json).unwrap(), text);
+
+        assert_eq!(
+            text.downsampled().to_json(),
+            r#"{"text":"","extra":[{"text":"Hello ","color":"red","bold":true},{"text":"world","color":"red"}]}"#
+        );
+        assert_eq!(Text::new().to_json(), r#"{"text":""}"#);
+    }
+}
@@ -0,0 +1,192 @@
+//! Leather armor dyeing and a dye recipe solver
+
+use super::dye::DyeColor;
+use crate::Color;
+
+/// Dyes that fit in a crafting grid next to the armor piece
+pub const MAX_DYES_PER_CRAFT: usize = 8;
+
+/// How many candidates the solver keeps at each step
+const BEAM_WIDTH: usize = 16;
+
+/// Running sums of vanilla's dye averaging
+#[derive(Debug, Clone, Copy, Default)]
+struct DyeMix {
+    totals: [u32; 3],
+    intensity: u32, // Sum of each color's largest channel
+    count: u32,
+}
+
+impl DyeMix {
+    fn from_base(base: Option<&Color>) -> DyeMix {
+        let mut mix = DyeMix::default();
+        if let Some(base) = base {
+            mix.add([base.r, base.g, base.b]);
+        }
+        mix
+    }
+
+    fn add(&mut self, rgb: [u8; 3]) {
+        for (total, channel) in self.totals.iter_mut().zip(rgb) {
+            *total += channel as u32;
+        }
+        self.intensity += *rgb.iter().max().unwrap() as u32;
+        self.count += 1;
+    }
+
+    fn add_dye(&mut self, dye: DyeColor) {
+        let color = dye.texture_color();
+        self.add([color.r, color.g, color.b]);
+    }
+
+    /// The averaged color, scaled back up to the average intensity
+    fn color(&self) -> Color {
+        let average = self.totals.map(|total| total / self.count);
+        let intensity = self.intensity as f32 / self.count as f32;
+        let max = *average.iter().max().unwrap() as f32;
+        let [r, g, b] = average.map(|c| (c as f32 * intensity / max) as u8);
+        Color::from_rgb(r, g, b)
+    }
+}
+
+/// The color of leather armor after crafting it with `dyes`
+///
+/// `base` is the armor's current color, or `None` if it is undyed. Returns
+/// `None` without dyes, since the recipe needs at least one.
+pub fn dye_leather(base: Option<&Color>, dyes: &[DyeColor]) -> Option<Color> {
+    if dyes.is_empty() {
+        return None;
+    }
+    let mut mix = DyeMix::from_base(base);
+    for &dye in dyes {
+        mix.add_dye(dye);
+    }
+    Some(mix.color())
+}
+
+/// A sequence of crafts that dyes undyed leather close to a target color
+#[derive(Debug, Clone, PartialEq)]
+pub struct LeatherRecipe {
+    pub steps: Vec<Vec<DyeColor>>, // The dyes of each craft, in order
+    pub color: Color,              // The resulting color
+}
+
+/// Candidate results of one craft from `base`, best first
+///
+/// Dye sets are built up one dye at a time in a beam, keeping them sorted so
+/// each multiset is only visited once.
+fn craft_candidates(base: Option<&Color>, target: &Color) -> Vec<(f32, Vec<DyeColor>, Color)> {
+    let start = DyeMix::from_base(base);
+    let mut partial: Vec<(Vec<DyeColor>, DyeMix)> = vec![(Vec::new(), start)];
+    let mut candidates = Vec::new();
+
+    for _ in 0..MAX_DYES_PER_CRAFT {
+        let mut next = Vec::new();
+        for (dyes, mix) in &partial {
+            let first = dyes.last().map_or(0, |dye| dye.id());
+            for &dye in &DyeColor::ALL[first as usize..] {
+                let mut mix = *mix;
+                mix.add_dye(dye);
+                let color = mix.color();
+                let mut dyes = dyes.clone();
+                dyes.push(dye);
+                next.push((target.oklab_distance(&color), dyes, mix, color));
+            }
+        }
+        next.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+        next.truncate(BEAM_WIDTH);
+
+        partial = next
+            .iter()
+            .map(|(_, dyes, mix, _)| (dyes.clone(), *mix))
+            .collect();
+        candidates.extend(
+            next.into_iter()
+                .map(|(distance, dyes, _, color)| (distance, dyes, color)),
+        );
+    }
+
+    candidates.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+    candidates.truncate(BEAM_WIDTH);
+    candidates
+}
+
+/// Search for crafts that dye undyed leather as close to `target` as
+/// possible, by Oklab distance, using at most `max_crafts` crafts
+///
+/// This is a beam search, so the recipe is good but not always optimal.
+pub fn leather_recipe(target: &Color, max_crafts: usize) -> Option<LeatherRecipe> {
+    let mut best: Option<(f32, LeatherRecipe)> = None;
+    let mut beam = vec![LeatherRecipe {
+        steps: Vec::new(),
+        color: Color::WHITE,
+    }];
+
+    for _ in 0..max_crafts {
+        let mut next: Vec<(f32, LeatherRecipe)> = Vec::new();
+        for recipe in &beam {
+            let base = (!recipe.steps.is_empty()).then_some(&recipe.color);
+            for (distance, dyes, color) in craft_candidates(base, target) {
+                let mut steps = recipe.steps.clone();
+                steps.push(dyes);
+                next.push((distance, LeatherRecipe { steps, color }));
+            }
+        }
+        next.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+        next.truncate(BEAM_WIDTH);
+
+        if let Some((distance, recipe)) = next.first()
+            && best.as_ref().is_none_or(|(best, _)| distance < best)
+        {
+            best = Some((*distance, recipe.clone()));
+        }
+        if best.as_ref().is_some_and(|(distance, _)| *distance == 0.0) {
+            break;
+        }
+        beam = next.into_iter().map(|(_, recipe)| recipe).collect();
+    }
+
+    best.map(|(_, recipe)| recipe)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dye_leather() {
+        // A single dye gives its texture color
+        let red = dye_leather(None, &[DyeColor::Red]).unwrap();
+        assert_eq!(red, DyeColor::Red.texture_color());
+        assert_eq!(red.to_minecraft_int(), 11546150);
+
+        // Red and yellow average to (215, 131, 49), already at full intensity
+        let orange = dye_leather(None, &[DyeColor::Red, DyeColor::Yellow]).unwrap();
+        assert_eq!(orange, Color::from_rgb(215, 131, 49));
+
+        // Red and blue average to (118, 57, 104), scaled up by 173 / 118
+        let redyed = dye_leather(Some(&red), &[DyeColor::Blue]).unwrap();
+        assert_eq!(redyed, Color::from_rgb(173, 83, 152));
+        assert_eq!(dye_leather(Some(&red), &[]), None);
+    }
+
+    #[test]
+    fn test_leather_recipe() {
+        let target = dye_leather(None, &[DyeColor::Lime, DyeColor::Cyan]).unwrap();
+        let recipe = leather_recipe(&target, 1).unwrap();
+        assert_eq!(recipe.color, target);
+        assert_eq!(recipe.steps.len(), 1);
+
+        // Replaying the steps gives the reported color
+        let target = Color::from_hex("#7A3D9E").unwrap();
+        let recipe = leather_recipe(&target, 2).unwrap();
+        let mut color = None;
+        for dyes in &recipe.steps {
+            assert!(!dyes.is_empty() && dyes.len() <= MAX_DYES_PER_CRAFT);
+            color = dye_leather(color.as_ref(), dyes);
+        }
+        assert_eq!(color, Some(recipe.color.clone()));
+        assert!(target.oklab_distance(&recipe.color) < 0.05);
+        assert_eq!(leather_recipe(&target, 0), None);
+    }
+}
@@ -0,0 +1,538 @@
+//! Legacy `§`/`&` formatted strings
+
+use super::{
+    Edition, HexFormat, hex_code_len, is_color_code, is_format_code, legacy_code, legacy_color,
+};
+use crate::{Color, Style, Text};
+use std::fmt;
+
+/// A malformed legacy code found by [`validate_legacy_codes`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MalformedCode {
+    pub position: usize, // Byte offset of the `§` or `&`
+    pub kind: MalformedCodeKind,
+}
+
+/// What is wrong with a malformed legacy code
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MalformedCodeKind {
+    /// `§` followed by a character that is not a code
+    UnknownCode(char),
+    /// `§` at the end of the text
+    DanglingPrefix,
+    /// `&#` or `§x` not followed by a complete hex color
+    IncompleteHex,
+}
+
+impl fmt::Display for MalformedCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.kind {
+            MalformedCodeKind::UnknownCode(c) => {
+                write!(f, "Unknown code '{}' at {}", c, self.position)
+            }
+            MalformedCodeKind::DanglingPrefix => {
+                write!(f, "Missing code after prefix at {}", self.position)
+            }
+            MalformedCodeKind::IncompleteHex => {
+                write!(f, "Incomplete hex color at {}", self.position)
+            }
+        }
+    }
+}
+
+/// Whether `c` is any legacy code, including `r` and the hex marker `x`
+fn is_code(c: char) -> bool {
+    is_color_code(c) || is_format_code(c) || matches!(c.to_ascii_lowercase(), 'r' | 'x')
+}
+
+impl Style {
+    /// Apply a legacy formatting code (`k` to `o`), returning false if unknown
+    fn apply_format_code(&mut self, code: char) -> bool {
+        match code.to_ascii_lowercase() {
+            'k' => self.obfuscated = true,
+            'l' => self.bold = true,
+            'm' => self.strikethrough = true,
+            'n' => self.underlined = true,
+            'o' => self.italic = true,
+            _ => return false,
+        }
+        true
+    }
+
+    /// The legacy formatting codes for the enabled decorations
+    fn format_codes(&self) -> Vec<char> {
+        [
+            (self.obfuscated, 'k'),
+            (self.bold, 'l'),
+            (self.strikethrough, 'm'),
+            (self.underlined, 'n'),
+            (self.italic, 'o'),
+        ]
+        .iter()
+        .filter(|(enabled, _)| *enabled)
+        .map(|&(_, code)| code)
+        .collect()
+    }
+}
+
+/// Read the color of a hex code of length `len` starting at `i`
+fn hex_code_color(chars: &[char], i: usize, len: usize) -> Color {
+    let digits: String = if len == 8 {
+        chars[i + 2..i + 8].iter().collect()
+    } else {
+        chars[i + 3..i + 14].iter().step_by(2).collect()
+    };
+    // The digits were validated by `hex_code_len`
+    Color::from_hex(&digits).unwrap()
+}
+
+/// A visible character or a code in a legacy formatted string
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum LegacyToken<'a> {
+    Char(char),
+    Color(Color, &'a str), // With the code as written
+    Format(char, &'a str),
+    Reset(&'a str),
+}
+
+impl LegacyToken<'_> {
+    /// Apply this token to a style, where `base` is the style `§r` returns to
+    pub(crate) fn apply(&self, style: &mut Style, base: &Style) {
+        match self {
+            LegacyToken::Char(_) => {}
+            LegacyToken::Color(color, _) => *style = Style::colored(color),
+            LegacyToken::Format(code, _) => {
+                style.apply_format_code(*code);
+            }
+            LegacyToken::Reset(_) => *style = base.clone(),
+        }
+    }
+}
+
+/// Split legacy formatted text into characters and codes of `edition`
+///
+/// Unknown codes are kept as characters.
+pub(crate) fn legacy_tokens<'a>(
+    text: &'a str,
+    prefixes: &[char],
+    edition: Edition,
+) -> Vec<LegacyToken<'a>> {
+    let indices: Vec<(usize, char)> = text.char_indices().collect();
+    let chars: Vec<char> = indices.iter().map(|&(_, c)| c).collect();
+    let raw = |i: usize, len: usize| {
+        let end = indices.get(i + len).map_or(text.len(), |&(end, _)| end);
+        &text[indices[i].0..end]
+    };
+
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        if prefixes.contains(&c) {
+            if let Some(len) = hex_code_len(&chars, i).filter(|_| edition == Edition::Java) {
+                tokens.push(LegacyToken::Color(
+                    hex_code_color(&chars, i, len),
+                    raw(i, len),
+                ));
+                i += len;
+                continue;
+            }
+            let token = match chars.get(i + 1) {
+                Some(&code) if is_color_code(code) => {
+                    Some(LegacyToken::Color(legacy_color(code).unwrap(), raw(i, 2)))
+                }
+                // Checked before formats, since Bedrock's `m` and `n` are colors
+                Some(&code) if edition.extra_color(code).is_some() => Some(LegacyToken::Color(
+                    edition.extra_color(code).unwrap(),
+                    raw(i, 2),
+                )),
+                Some(&code) if code.eq_ignore_ascii_case(&'r') => {
+                    Some(LegacyToken::Reset(raw(i, 2)))
+                }
+                Some(&code) if is_format_code(code) => {
+                    Some(LegacyToken::Format(code.to_ascii_lowercase(), raw(i, 2)))
+                }
+                _ => None,
+            };
+            if let Some(token) = token {
+                tokens.push(token);
+                i += 2;
+                continue;
+            }
+        }
+        tokens.push(LegacyToken::Char(c));
+        i += 1;
+    }
+
+    tokens
+}
+
+impl Text {
+    /// Parse a legacy formatted string such as `"§c§lHello &#FF5733world"`
+    ///
+    /// Both `§` and `&` prefixes are accepted, along with `&#RRGGBB` and
+    /// `&x&R&R&G&G&B&B` hex codes. As in vanilla, a color code resets all
+    /// decorations and `§r` resets the whole style. Unknown codes are kept
+    /// as plain text.
+    pub fn from_legacy(text: &str) -> Text {
+        Text::from_legacy_in(text, Edition::Java)
+    }
+
+    /// Parse a legacy formatted string with the codes of `edition`
+    ///
+    /// Bedrock adds `§g` and the material codes but has no hex codes, and its
+    /// `§m` and `§n` are colors instead of strikethrough and underline.
+    pub fn from_legacy_in(text: &str, edition: Edition) -> Text {
+        let mut result = Text::new();
+        result.push_legacy(text, &Style::default(), &['&', '§'], edition);
+        result
+    }
+
+    /// Append legacy formatted text starting from `base`, which `§r` returns to
+    pub(crate) fn push_legacy(
+        &mut self,
+        text: &str,
+        base: &Style,
+        prefixes: &[char],
+        edition: Edition,
+    ) {
+        let mut style = base.clone();
+        let mut run = String::new();
+
+        for token in legacy_tokens(text, prefixes, edition) {
+            match token {
+                LegacyToken::Char(c) => run.push(c),
+                code => {
+                    self.push(&run, &style);
+                    run.clear();
+                    code.apply(&mut style, base);
+                }
+            }
+        }
+        self.push(&run, &style);
+    }
+
+    /// Serialize to a legacy formatted string
+    ///
+    /// The 16 legacy colors use their single-character codes and all other
+    /// colors use `format`, whose prefix is also used for every other code.
+    pub fn to_legacy(&self, format: HexFormat) -> String {
+        let prefix = format.prefix();
+        let mut result = String::new();
+        let mut current = Style::default();
+
+        for span in &self.spans {
+            let style = &span.style;
+            let lost_decoration = current
+                .format_codes()
+                .iter()
+                .any(|code| !style.format_codes().contains(code));
+
+            if style.color != current.color || lost_decoration {
+                match &style.color {
+                    Some(color) => match legacy_code(color) {
+                        Some(code) => {
+                            result.push(prefix);
+                            result.push(code);
+                        }
+                        None => result.push_str(&format.code(color)),
+                    },
+                    None => {
+                        result.push(prefix);
+                        result.push('r');
+                    }
+                }
+                current = Style {
+                    color: style.color.clone(),
+                    ..Style::default()
+                };
+            }
+
+            for code in style.format_codes() {
+                if !current.format_codes().contains(&code) {
+                    result.push(prefix);
+                    result.push(code);
+                }
+            }
+            current = style.clone();
+            result.push_str(&span.text);
+        }
+
+        result
+    }
+
+    /// Replace every color with the nearest of the 16 legacy colors
+    ///
+    /// Clients before 1.16 only understand the legacy colors.
+    pub fn downsampled(&self) -> Text {
+        let mut result = Text::new();
+        for span in &self.spans {
+            let mut style = span.style.clone();
+            style.color = style.color.map(|color| {
+                let code = color.to_minecraft_code().chars().nth(1).unwrap();
+                legacy_color(code).unwrap()
+            });
+            result.push(&span.text, &style);
+        }
+        result
+    }
+}
+
+/// Remove all `§` color and formatting codes, keeping only the visible text
+///
+/// As in vanilla, `&` is ordinary text, so `"R&D"` is left alone. Translate
+/// `&` codes with [`translate_legacy_codes`] first to strip them as well.
+pub fn strip_legacy_codes(text: &str) -> String {
+    legacy_tokens(text, &['§'], Edition::Java)
+        .into_iter()
+        .filter_map(|token| match token {
+            LegacyToken::Char(c) => Some(c),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Translate `&` codes to `§`, like Bukkit's `translateAlternateColorCodes`
+///
+/// Only an `&` followed by a valid code is translated, so text such as
+/// `"Fish & Chips"` is left alone. `&#RRGGBB` becomes `§x§R§R§G§G§B§B`,
+/// which the vanilla client understands.
+pub fn translate_legacy_codes(text: &str) -> String {
+    let chars: Vec<char> = text.chars().collect();
+    let mut result = String::with_capacity(text.len());
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        if c == '&' {
+            if let Some(len) = hex_code_len(&chars, i) {
+                result.push_str(&HexFormat::SectionX.code(&hex_code_color(&chars, i, len)));
+                i += len;
+                continue;
+            }
+            if chars.get(i + 1).is_some_and(|&code| is_code(code)) {
+                result.push('§');
+                i += 1;
+                continue;
+            }
+        }
+        result.push(c);
+        i += 1;
+    }
+
+    result
+}
+
+/// Replace hex color codes with the nearest legacy color code
+///
+/// Clients before 1.16 show hex codes as garbage. Each code keeps its prefix,
+/// so `&#FF5733` becomes `&c` and `§x§F§F§5§7§3§3` becomes `§c`.
+pub fn downsample_legacy_codes(text: &str) -> String {
+    let chars: Vec<char> = text.chars().collect();
+    let mut result = String::with_capacity(text.len());
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        if (c == '&' || c == '§')
+            && let Some(len) = hex_code_len(&chars, i)
+        {
+            let code = hex_code_color(&chars, i, len).to_minecraft_code();
+            result.push(c);
+            result.push_str(&code['§'.len_utf8()..]);
+            i += len;
+            continue;
+        }
+        result.push(c);
+        i += 1;
+    }
+
+    result
+}
+
+/// Find malformed codes instead of failing on the first one
+///
+/// Every `§` must start a valid code. An `&` is only reported when it starts
+/// an incomplete hex color, since it is also ordinary text.
+pub fn validate_legacy_codes(text: &str) -> Vec<MalformedCode> {
+    let chars: Vec<(usize, char)> = text.char_indices().collect();
+    let plain: Vec<char> = chars.iter().map(|&(_, c)| c).collect();
+    let mut issues = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let (position, c) = chars[i];
+        if c != '&' && c != '§' {
+            i += 1;
+            continue;
+        }
+
+        if let Some(len) = hex_code_len(&plain, i) {
+            i += len;
+            continue;
+        }
+        let kind = match plain.get(i + 1) {
+            Some('#' | 'x' | 'X') => Some(MalformedCodeKind::IncompleteHex),
+            Some(_) if c == '&' => None,
+            Some(&code) if is_code(code) => None,
+            Some(&code) => Some(MalformedCodeKind::UnknownCode(code)),
+            None if c == '&' => None,
+            None => Some(MalformedCodeKind::DanglingPrefix),
+        };
+        if let Some(kind) = kind {
+            issues.push(MalformedCode { position, kind });
+        }
+        // A prefix that does not start a code may be followed by another one
+        i += if plain.get(i + 1).is_some_and(|&code| is_code(code)) {
+            2
+        } else {
+            1
+        };
+    }
+
+    issues
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_legacy() {
+        let text = Text::from_legacy("§c§lHello &#FF5733world&r!");
+        assert_eq!(text.spans.len(), 3);
+
+        assert_eq!(text.spans[0].text, "Hello ");
+        assert_eq!(text.spans[0].style.color, Some(Color::MC_RED));
+        assert!(text.spans[0].style.bold);
+
+        // A color code resets decorations
+        assert_eq!(text.spans[1].text, "world");
+        assert_eq!(
+            text.spans[1].style,
+            Style::colored(&Color::from_rgb(255, 87, 51))
+        );
+
+        assert_eq!(text.spans[2].style, Style::default());
+    }
+
+    #[test]
+    fn test_from_legacy_edge_cases() {
+        // Decorations before a color are lost, unknown codes stay literal
+        let text = Text::from_legacy("&l&9Blue &x&0&0&F&F&0&0green&z");
+        assert_eq!(text.spans[0].style, Style::colored(&Color::MC_BLUE));
+        assert_eq!(text.spans[1].text, "green&z");
+        assert_eq!(text.spans[1].style.color, Some(Color::from_rgb(0, 255, 0)));
+
+        assert_eq!(Text::from_legacy("&").plain(), "&");
+        assert!(Text::from_legacy("§a§l").spans.is_empty());
+    }
+
+    #[test]
+    fn test_from_legacy_in() {
+        let text = Text::from_legacy_in("§g§lGold §mRedstone &#FF5733", Edition::Bedrock);
+        assert_eq!(text.spans[0].style.color, Some(Color::MC_MINECOIN_GOLD));
+        assert!(text.spans[0].style.bold);
+        assert_eq!(text.spans[1].text, "Redstone &#FF5733");
+        assert_eq!(
+            text.spans[1].style,
+            Style::colored(&Color::MC_MATERIAL_REDSTONE)
+        );
+
+        // Java has no `§g`, and `§m` is strikethrough
+        let text = Text::from_legacy("§g§mA");
+        assert_eq!(text.plain(), "§gA");
+        assert!(text.spans[1].style.strikethrough);
+    }
+
+    #[test]
+    fn test_to_legacy() {
+        let legacy = "§c§lHello §x§F§F§5§7§3§3world§r!";
+        let text = Text::from_legacy(legacy);
+        assert_eq!(text.to_legacy(HexFormat::SectionX), legacy);
+        assert_eq!(
+            text.to_legacy(HexFormat::Ampersand),
+            "&c&lHello &#FF5733world&r!"
+        );
+
+        // Dropping a decoration needs the color to be re-sent
+        let text = Text::from_legacy("&6&l&oA&6&lB");
+        assert_eq!(text.to_legacy(HexFormat::Ampersand), "&6&l&oA&6&lB");
+    }
+
+    #[test]
+    fn test_downsampled() {
+        let text = Text::from_legacy("&#FF5733Hot &#FF4040Red");
+        let downsampled = text.downsampled();
+        assert_eq!(downsampled.spans.len(), 1);
+        assert_eq!(downsampled.to_legacy(HexFormat::Ampersand), "&cHot Red");
+    }
+
+    #[test]
+    fn test_strip_and_translate() {
+        assert_eq!(
+            strip_legacy_codes("§c§lHello §x§F§F§5§7§3§3world§r!"),
+            "Hello world!"
+        );
+        assert_eq!(
+            strip_legacy_codes("§aRock&roll R&D &#FF5733"),
+            "Rock&roll R&D &#FF5733"
+        );
+        assert_eq!(
+            translate_legacy_codes("&cR&D & &lbold &#FF5733hex &z"),
+            "§cR§D & §lbold §x§F§F§5§7§3§3hex &z"
+        );
+    }
+
+    #[test]
+    fn test_downsample_legacy_codes() {
+        assert_eq!(
+            downsample_legacy_codes("&#FF5733Hot §x§5§5§5§5§F§FCool &lkept"),
+            "&cHot §9Cool &lkept"
+        );
+    }
+
+    #[test]
+    fn test_validate_legacy_codes() {
+        assert!(validate_legacy_codes("§aOK & fine &#123456").is_empty());
+
+        let issues = validate_legacy_codes("é§zbad &#12 §x§F§F end§");
+        assert_eq!(
+            issues,
+            [
+                MalformedCode {
+                    position: 2,
+                    kind: MalformedCodeKind::UnknownCode('z')
+                },
+                MalformedCode {
+                    position: 9,
+                    kind: MalformedCodeKind::IncompleteHex
+                },
+                MalformedCode {
+                    position: 14,
+                    kind: MalformedCodeKind::IncompleteHex
+                },
+                MalformedCode {
+                    position: 27,
+                    kind: MalformedCodeKind::DanglingPrefix
+                },
+            ]
+        );
+        assert_eq!(issues[0].to_string(), "Unknown code 'z' at 2");
+
+        // The `&` before a malformed code is plain text, not part of it
+        assert_eq!(
+            validate_legacy_codes("&&#12 §§a"),
+            [
+                MalformedCode {
+                    position: 1,
+                    kind: MalformedCodeKind::IncompleteHex
+                },
+                MalformedCode {
+                    position: 6,
+                    kind: MalformedCodeKind::UnknownCode('§')
+                },
+            ]
+        );
+    }
+}
@@ -0,0 +1,376 @@
+//! Map item colors and map art conversion
+
+use super::command::DATA_VERSION_1_21_4;
+use super::nbt::{Tag, gzip};
+use crate::space::linear_to_oklab;
+use crate::{Color, ColorError};
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+/// Width and height of a map in pixels
+pub const MAP_SIZE: usize = 128;
+
+/// Java Edition base map colors, indexed by base ID; 0 is transparent
+pub(crate) const BASE_COLORS: [u32; 62] = [
+    0x000000, 0x7FB238, 0xF7E9A3, 0xC7C7C7, 0xFF0000, 0xA0A0FF, 0xA7A7A7, 0x007C00, 0xFFFFFF,
+    0xA4A8B8, 0x976D4D, 0x707070, 0x4040FF, 0x8F7748, 0xFFFCF5, 0xD87F33, 0xB24CD8, 0x6699D8,
+    0xE5E533, 0x7FCC19, 0xF27FA5, 0x4C4C4C, 0x999999, 0x4C7F99, 0x7F3FB2, 0x334CB2, 0x664C33,
+    0x667F33, 0x993333, 0x191919, 0xFAEE4D, 0x5CDBD5, 0x4A80FF, 0x00D93A, 0x815631, 0x700200,
+    0xD1B1A1, 0x9F5224, 0x95576C, 0x706C8A, 0xBA8524, 0x677535, 0xA04D4E, 0x392923, 0x876B62,
+    0x575C5C, 0x7A4958, 0x4C3E5C, 0x4C3223, 0x4C522A, 0x8E3C2E, 0x251610, 0xBD3031, 0x943F61,
+    0x5C191D, 0x167E86, 0x3A8E8C, 0x562C3E, 0x14B485, 0x646464, 0xD8AF93, 0x7FA796,
+];
+
+/// Brightness multipliers (out of 255) for the 4 shades of each base color
+///
+/// Shades 0 to 2 come from blocks lower than, level with and higher than the
+/// block to the north; shade 3 cannot be placed in survival.
+const SHADE_MULTIPLIERS: [u32; 4] = [180, 220, 255, 135];
+
+/// The color of a map color ID (`base * 4 + shade`), or `None` for the
+/// transparent IDs 0 to 3 and unknown IDs
+pub fn map_color(id: u8) -> Option<Color> {
+    let base = *BASE_COLORS.get(id as usize / 4).filter(|_| id >= 4)?;
+    let multiplier = SHADE_MULTIPLIERS[id as usize % 4];
+    let channel = |shift: u32| ((base >> shift & 0xFF) * multiplier / 255) as u8;
+    Some(Color::from_rgb(channel(16), channel(8), channel(0)))
+}
+
+/// Every opaque map color with its ID
+pub fn map_palette() -> Vec<(u8, Color)> {
+    (4..BASE_COLORS.len() as u8 * 4)
+        .filter_map(|id| map_color(id).map(|color| (id, color)))
+        .collect()
+}
+
+/// Options for [`map_art`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct MapArtOptions {
+    pub dithering: bool,      // Diffuse the error with Floyd–Steinberg
+    pub staircase_only: bool, // Only use the 3 shades that blocks can produce
+}
+
+impl MapArtOptions {
+    /// Nearest colors with all 4 shades
+    pub fn new() -> Self {
+        MapArtOptions::default()
+    }
+
+    /// Spread each pixel's error to its neighbors with Floyd–Steinberg
+    pub fn with_dithering(mut self) -> Self {
+        self.dithering = true;
+        self
+    }
+
+    /// Skip the darkest shade, which survival builds cannot produce
+    pub fn with_staircase_only(mut self) -> Self {
+        self.staircase_only = true;
+        self
+    }
+}
+
+/// Map colors with their Oklab coordinates, for repeated lookups
+struct Palette {
+    entries: Vec<(u8, Color, [f32; 3])>,
+}
+
+impl Palette {
+    fn new(staircase_only: bool) -> Palette {
+        let entries = map_palette()
+            .into_iter()
+            .filter(|(id, _)| !staircase_only || id % 4 != 3)
+            .map(|(id, color)| {
+                let lab = linear_to_oklab(color.to_linear_rgb());
+                (id, color, lab)
+            })
+            .collect();
+        Palette { entries }
+    }
+
+    /// The palette for a mode, built on first use
+    fn cached(staircase_only: bool) -> &'static Palette {
+        static FULL: OnceLock<Palette> = OnceLock::new();
+        static STAIRCASE: OnceLock<Palette> = OnceLock::new();
+        let cell = if staircase_only { &STAIRCASE } else { &FULL };
+        cell.get_or_init(|| Palette::new(staircase_only))
+    }
+
+    /// The entry closest to `color` in Oklab
+    fn nearest(&self, color: &Color) -> &(u8, Color, [f32; 3]) {
+        let lab = linear_to_oklab(color.to_linear_rgb());
+        let distance = |other: &[f32; 3]| (0..3).map(|i| (lab[i] - other[i]).powi(2)).sum::<f32>();
+        self.entries
+            .iter()
+            .min_by(|a, b| distance(&a.2).partial_cmp(&distance(&b.2)).unwrap())
+            .unwrap()
+    }
+}
+
+impl Color {
+    /// The ID of the closest opaque map color, by Oklab distance
+    pub fn to_map_color_id(&self) -> u8 {
+        Palette::cached(false).nearest(self).0
+    }
+
+    /// The ID of the closest map color a staircase build can produce
+    pub fn to_staircase_map_color_id(&self) -> u8 {
+        Palette::cached(true).nearest(self).0
+    }
+}
+
+/// Convert a 128×128 RGBA buffer (row by row, 4 bytes per pixel) into map
+/// color IDs
+///
+/// Pixels with alpha below 128 become transparent (ID 0).
+pub fn map_art(rgba: &[u8], options: MapArtOptions) -> Result<Vec<u8>, ColorError> {
+    if rgba.len() != MAP_SIZE * MAP_SIZE * 4 {
+        return Err(ColorError::InvalidPixelBuffer);
+    }
+
+    let palette = Palette::cached(options.staircase_only);
+    let mut pixels: Vec<[f32; 3]> = rgba
+        .chunks_exact(4)
+        .map(|p| [p[0] as f32, p[1] as f32, p[2] as f32])
+        .collect();
+    let mut ids = vec![0; MAP_SIZE * MAP_SIZE];
+
+    for y in 0..MAP_SIZE {
+        for x in 0..MAP_SIZE {
+            let i = y * MAP_SIZE + x;
+            if rgba[i * 4 + 3] < 128 {
+                continue;
+            }
+
+            let [r, g, b] = pixels[i].map(|c| c.round().clamp(0.0, 255.0) as u8);
+            let (id, color, _) = palette.nearest(&Color::from_rgb(r, g, b));
+            ids[i] = *id;
+            if !options.dithering {
+                continue;
+            }
+
+            let chosen = [color.r, color.g, color.b];
+            let error: [f32; 3] = std::array::from_fn(|c| pixels[i][c] - chosen[c] as f32);
+            let neighbors = [(1, 0, 7.0), (-1, 1, 3.0), (0, 1, 5.0), (1, 1, 1.0)];
+            for (dx, dy, weight) in neighbors {
+                let (nx, ny) = (x as isize + dx, y + dy);
+                if nx < 0 || nx >= MAP_SIZE as isize || ny >= MAP_SIZE {
+                    continue;
+                }
+                let neighbor = &mut pixels[ny * MAP_SIZE + nx as usize];
+                for c in 0..3 {
+                    neighbor[c] += error[c] * weight / 16.0;
+                }
+            }
+        }
+    }
+
+    Ok(ids)
+}
+
+/// The first data version (1.16) that stores the dimension as an ID string
+const STRING_DIMENSION_VERSION: i32 = 2566;
+
+/// The contents of a `map_<id>.dat` file
+#[derive(Debug, Clone, PartialEq)]
+pub struct MapData {
+    pub colors: Vec<u8>,    // 128×128 map color IDs, row by row
+    pub scale: u8,          // 0 (1:1) to 4 (1:16)
+    pub dimension: String,  // e.g. `minecraft:overworld`
+    pub locked: bool,       // Locked in a cartography table
+    pub center: (i32, i32), // World x and z of the map center
+    pub data_version: i32,  // The game data version the file is written for
+}
+
+impl MapData {
+    /// A locked overworld map at scale 0, for 1.21.4
+    pub fn new(colors: Vec<u8>) -> Result<MapData, ColorError> {
+        if colors.len() != MAP_SIZE * MAP_SIZE {
+            return Err(ColorError::InvalidPixelBuffer);
+        }
+        Ok(MapData {
+            colors,
+            scale: 0,
+            dimension: "minecraft:overworld".to_string(),
+            locked: true,
+            center: (0, 0),
+            data_version: DATA_VERSION_1_21_4,
+        })
+    }
+
+    /// Set the scale, clamped to 4
+    pub fn with_scale(mut self, scale: u8) -> Self {
+        self.scale = scale.min(4);
+        self
+    }
+
+    /// Set the dimension ID, e.g. `minecraft:the_nether`
+    pub fn with_dimension(mut self, dimension: &str) -> Self {
+        self.dimension = dimension.to_string();
+        self
+    }
+
+    /// Set whether the map is locked
+    pub fn with_locked(mut self, locked: bool) -> Self {
+        self.locked = locked;
+        self
+    }
+
+    /// Set the world coordinates of the map center
+    pub fn with_center(mut self, x: i32, z: i32) -> Self {
+        self.center = (x, z);
+        self
+    }
+
+    /// Set the data version of the game the file is for
+    pub fn with_data_version(mut self, data_version: i32) -> Self {
+        self.data_version = data_version;
+        self
+    }
+
+    /// The dimension as written for the data version
+    ///
+    /// Before 1.16 it was a byte: 0 for the overworld, -1 for the nether and
+    /// 1 for the end.
+    fn dimension_tag(&self) -> Tag {
+        if self.data_version >= STRING_DIMENSION_VERSION {
+            return Tag::String(self.dimension.clone());
+        }
+        Tag::Byte(match self.dimension.as_str() {
+            "minecraft:the_nether" => -1,
+            "minecraft:the_end" => 1,
+            _ => 0,
+        })
+    }
+
+    /// The uncompressed NBT
+    pub fn to_nbt(&self) -> Vec<u8> {
+        let data = Tag::Compound(vec![
+            ("scale".to_string(), Tag::Byte(self.scale as i8)),
+            ("dimension".to_string(), self.dimension_tag()),
+            ("locked".to_string(), Tag::Byte(self.locked.into())),
+            ("trackingPosition".to_string(), Tag::Byte(0)),
+            ("unlimitedTracking".to_string(), Tag::Byte(0)),
+            ("xCenter".to_string(), Tag::Int(self.center.0)),
+            ("zCenter".to_string(), Tag::Int(self.center.1)),
+            ("banners".to_string(), Tag::List(Vec::new())),
+            ("frames".to_string(), Tag::List(Vec::new())),
+            ("colors".to_string(), Tag::ByteArray(self.colors.clone())),
+        ]);
+        let root = Tag::Compound(vec![
+            ("data".to_string(), data),
+            ("DataVersion".to_string(), Tag::Int(self.data_version)),
+        ]);
+        root.to_bytes("")
+    }
+
+    /// The gzipped NBT, as the game reads it
+    pub fn to_dat(&self) -> Vec<u8> {
+        gzip(&self.to_nbt())
+    }
+
+    /// Write `map_<id>.dat` into a world's `data` directory
+    pub fn save(&self, dir: &Path, id: u32) -> std::io::Result<PathBuf> {
+        let path = dir.join(format!("map_{}.dat", id));
+        std::fs::write(&path, self.to_dat())?;
+        Ok(path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_map_colors() {
+        // GRASS (base 1) at each shade
+        assert_eq!(map_color(4).unwrap().to_hex(), "#597D27");
+        assert_eq!(map_color(5).unwrap().to_hex(), "#6D9930");
+        assert_eq!(map_color(6).unwrap().to_hex(), "#7FB238");
+        assert_eq!(map_color(7).unwrap().to_hex(), "#435E1D");
+        assert_eq!(map_color(2), None);
+        assert_eq!(map_color(248), None);
+        assert_eq!(map_palette().len(), 61 * 4);
+
+        assert_eq!(Color::from_rgb(255, 252, 245).to_map_color_id(), 14 * 4 + 2);
+        let darkest_red = map_color(28 * 4 + 3).unwrap();
+        assert_eq!(darkest_red.to_map_color_id(), 28 * 4 + 3);
+        assert_ne!(darkest_red.to_staircase_map_color_id() % 4, 3);
+    }
+
+    #[test]
+    fn test_map_art() {
+        assert_eq!(
+            map_art(&[0; 16], MapArtOptions::new()),
+            Err(ColorError::InvalidPixelBuffer)
+        );
+
+        // Left half snow white, right half transparent
+        let mut rgba = vec![0; MAP_SIZE * MAP_SIZE * 4];
+        for (i, pixel) in rgba.chunks_exact_mut(4).enumerate() {
+            if i % MAP_SIZE < MAP_SIZE / 2 {
+                pixel.copy_from_slice(&[255, 255, 255, 255]);
+            }
+        }
+        let ids = map_art(&rgba, MapArtOptions::new().with_dithering()).unwrap();
+        assert_eq!(ids.len(), MAP_SIZE * MAP_SIZE);
+        assert_eq!(ids[0], 8 * 4 + 2);
+        assert_eq!(ids[MAP_SIZE - 1], 0);
+    }
+
+    #[test]
+    fn test_dithering_mixes_shades() {
+        // A gray between two palette grays dithers into a mix of both
+        let rgba: Vec<u8> = [120, 120, 120, 255].repeat(MAP_SIZE * MAP_SIZE);
+        let flat = map_art(&rgba, MapArtOptions::new().with_staircase_only()).unwrap();
+        assert!(flat.iter().all(|&id| id == flat[0]));
+
+        let options = MapArtOptions::new().with_staircase_only().with_dithering();
+        let dithered = map_art(&rgba, options).unwrap();
+        assert!(dithered.iter().any(|&id| id != dithered[0]));
+        assert!(dithered.iter().all(|&id| id % 4 != 3));
+    }
+
+    #[test]
+    fn test_map_data() {
+        use super::super::nbt::read::{gunzip, parse};
+
+        assert_eq!(
+            MapData::new(vec![0; 10]),
+            Err(ColorError::InvalidPixelBuffer)
+        );
+
+        let mut colors = vec![0; MAP_SIZE * MAP_SIZE];
+        colors[0] = 8 * 4 + 2;
+        let map = MapData::new(colors.clone()).unwrap().with_scale(9);
+        let (name, root) = parse(&gunzip(&map.to_dat()).unwrap()).unwrap();
+        assert_eq!(name, "");
+        let Tag::Compound(root) = root else { panic!() };
+        assert_eq!(
+            root[1],
+            ("DataVersion".to_string(), Tag::Int(DATA_VERSION_1_21_4))
+        );
+        let Tag::Compound(data) = &root[0].1 else {
+            panic!()
+        };
+        let field = |key: &str| data.iter().find(|(k, _)| k == key).map(|(_, v)| v.clone());
+        assert_eq!(field("scale"), Some(Tag::Byte(4)));
+        assert_eq!(field("locked"), Some(Tag::Byte(1)));
+        assert_eq!(field("colors"), Some(Tag::ByteArray(colors)));
+        assert_eq!(
+            field("dimension"),
+            Some(Tag::String("minecraft:overworld".to_string()))
+        );
+
+        // 1.15.2 stores the dimension as a byte
+        let old = map
+            .with_dimension("minecraft:the_nether")
+            .with_data_version(2230);
+        let (_, Tag::Compound(root)) = parse(&old.to_nbt()).unwrap() else {
+            panic!()
+        };
+        let Tag::Compound(data) = &root[0].1 else {
+            panic!()
+        };
+        assert!(data.contains(&("dimension".to_string(), Tag::Byte(-1))));
+    }
+}
@@ -0,0 +1,415 @@
+//! MiniMessage tags, as used by Paper and Adventure
+
+use super::{color_name, named_color};
+use crate::{Color, ColorSpace, Gradient, Style, Text};
+
+/// A decoration flag of a style
+type Decoration = fn(&mut Style) -> &mut bool;
+
+/// A tag that changes the style of its contents
+enum Tag {
+    Color(Color),
+    Decoration(Decoration, bool),
+    Gradient(Vec<Color>),
+    Rainbow(bool, i32), // Reversed, and the phase in tenths of a turn
+}
+
+enum Node {
+    Text(String),
+    Tag(Tag, Vec<Node>),
+}
+
+/// The decoration flag for a tag name or alias
+fn decoration(name: &str) -> Option<(&'static str, Decoration)> {
+    let decoration: (&str, Decoration) = match name {
+        "bold" | "b" => ("bold", |s| &mut s.bold),
+        "italic" | "i" | "em" => ("italic", |s| &mut s.italic),
+        "underlined" | "u" => ("underlined", |s| &mut s.underlined),
+        "strikethrough" | "st" => ("strikethrough", |s| &mut s.strikethrough),
+        "obfuscated" | "obf" => ("obfuscated", |s| &mut s.obfuscated),
+        _ => return None,
+    };
+    Some(decoration)
+}
+
+/// A color argument: a named color or `#RRGGBB`
+fn parse_color(arg: &str) -> Option<Color> {
+    if arg.starts_with('#') {
+        return (arg.len() == 7).then(|| Color::from_hex(arg).ok())?;
+    }
+    match arg {
+        "grey" => named_color("gray"),
+        "dark_grey" => named_color("dark_gray"),
+        name => named_color(name),
+    }
+}
+
+/// Parse the contents of `<...>` into the canonical name used to close the
+/// tag and the tag itself
+fn parse_tag(content: &str) -> Option<(&'static str, Tag)> {
+    let lower = content.to_ascii_lowercase();
+    let mut args = lower.split(':');
+    let name = args.next()?;
+    let args: Vec<&str> = args.collect();
+
+    if let Some(name) = name.strip_prefix('!') {
+        let (name, flag) = decoration(name)?;
+        return args
+            .is_empty()
+            .then_some((name, Tag::Decoration(flag, false)));
+    }
+    if let Some((name, flag)) = decoration(name) {
+        let enabled = match args.as_slice() {
+            [] | ["true"] => true,
+            ["false"] => false,
+            _ => return None,
+        };
+        return Some((name, Tag::Decoration(flag, enabled)));
+    }
+
+    match (name, args.as_slice()) {
+        ("gradient", args) => {
+            // Only the last argument may be a phase, which is ignored
+            let colors = match args.split_last() {
+                Some((last, rest)) if last.parse::<f32>().is_ok() => rest,
+                _ => args,
+            };
+            let colors = colors
+                .iter()
+                .map(|arg| parse_color(arg))
+                .collect::<Option<Vec<_>>>()?;
+            let colors = match colors.len() {
+                0 => vec![Color::WHITE, Color::BLACK],
+                1 => return None,
+                _ => colors,
+            };
+            Some(("gradient", Tag::Gradient(colors)))
+        }
+        ("rainbow", args) => {
+            // `<rainbow:[!][phase]>`, as in Adventure
+            let arg = match args {
+                [] => "",
+                [arg] => arg,
+                _ => return None,
+            };
+            let phase = arg.strip_prefix('!').unwrap_or(arg);
+            let phase = if phase.is_empty() {
+                0
+            } else {
+                phase.parse().ok()?
+            };
+            Some(("rainbow", Tag::Rainbow(arg.starts_with('!'), phase)))
+        }
+        ("color" | "colour" | "c", [color]) => Some(("color", Tag::Color(parse_color(color)?))),
+        (color, []) => Some(("color", Tag::Color(parse_color(color)?))),
+        _ => None,
+    }
+}
+
+/// The canonical name of a closing tag such as `</b>` or `</color>`
+fn closing_name(content: &str) -> Option<&'static str> {
+    let lower = content.to_ascii_lowercase();
+    let name = lower.split(':').next()?;
+    match name {
+        "color" | "colour" | "c" => Some("color"),
+        "gradient" => Some("gradient"),
+        "rainbow" => Some("rainbow"),
+        name => decoration(name.trim_start_matches('!'))
+            .map(|(name, _)| name)
+            .or_else(|| parse_color(name).map(|_| "color")),
+    }
+}
+
+/// Parse MiniMessage into a tree of tags, keeping unknown tags as text
+fn parse_tree(input: &str) -> Vec<Node> {
+    // Open tags with their canonical names and the nodes before them
+    let mut stack: Vec<(&'static str, Tag, Vec<Node>)> = Vec::new();
+    let mut nodes: Vec<Node> = Vec::new();
+    let mut run = String::new();
+
+    let close = |stack: &mut Vec<(&'static str, Tag, Vec<Node>)>, nodes: &mut Vec<Node>| {
+        let (_, tag, parent) = stack.pop().unwrap();
+        let children = std::mem::replace(nodes, parent);
+        nodes.push(Node::Tag(tag, children));
+    };
+
+    let mut chars = input.char_indices().peekable();
+    while let Some((i, c)) = chars.next() {
+        if c == '\\'
+            && let Some(&(_, next @ ('<' | '\\'))) = chars.peek()
+        {
+            run.push(next);
+            chars.next();
+            continue;
+        }
+
+        let content = if c == '<' {
+            input[i + 1..]
+                .find(['<', '>'])
+                .filter(|&end| input[i + 1 + end..].starts_with('>'))
+                .map(|end| &input[i + 1..i + 1 + end])
+        } else {
+            None
+        };
+        let Some(content) = content else {
+            run.push(c);
+            continue;
+        };
+
+        let handled = if content.eq_ignore_ascii_case("reset") {
+            flush(&mut run, &mut nodes);
+            while !stack.is_empty() {
+                close(&mut stack, &mut nodes);
+            }
+            true
+        } else if let Some(name) = content.strip_prefix('/') {
+            let name = closing_name(name);
+            let open = stack.iter().rposition(|(open, _, _)| Some(*open) == name);
+            if let Some(open) = open {
+                flush(&mut run, &mut nodes);
+                while stack.len() > open {
+                    close(&mut stack, &mut nodes);
+                }
+            }
+            open.is_some()
+        } else if let Some((name, tag)) = parse_tag(content) {
+            flush(&mut run, &mut nodes);
+            stack.push((name, tag, std::mem::take(&mut nodes)));
+            true
+        } else {
+            false
+        };
+
+        if handled {
+            // Skip past the closing `>`
+            for _ in 0..content.chars().count() + 1 {
+                chars.next();
+            }
+        } else {
+            run.push(c);
+        }
+    }
+
+    flush(&mut run, &mut nodes);
+    while !stack.is_empty() {
+        close(&mut stack, &mut nodes);
+    }
+    nodes
+}
+
+fn flush(run: &mut String, nodes: &mut Vec<Node>) {
+    if !run.is_empty() {
+        nodes.push(Node::Text(std::mem::take(run)));
+    }
+}
+
+/// Characters colored by an enclosing gradient, excluding recolored tags
+fn gradient_len(nodes: &[Node]) -> usize {
+    nodes
+        .iter()
+        .map(|node| match node {
+            Node::Text(text) => text.chars().count(),
+            Node::Tag(Tag::Color(_) | Tag::Gradient(_) | Tag::Rainbow(..), _) => 0,
+            Node::Tag(_, children) => gradient_len(children),
+        })
+        .sum()
+}
+
+fn flatten(
+    nodes: &[Node],
+    style: &Style,
+    colors: &mut Option<std::vec::IntoIter<Color>>,
+    text: &mut Text,
+) {
+    for node in nodes {
+        match node {
+            Node::Text(run) => match colors {
+                Some(colors) => {
+                    for c in run.chars() {
+                        let mut style = style.clone();
+                        style.color = colors.next().or(style.color);
+                        text.push(c.encode_utf8(&mut [0; 4]), &style);
+                    }
+                }
+                None => text.push(run, style),
+            },
+            Node::Tag(tag, children) => {
+                let mut style = style.clone();
+                let mut inner = None;
+                match tag {
+                    Tag::Color(color) => style.color = Some(color.clone()),
+                    Tag::Decoration(flag, enabled) => *flag(&mut style) = *enabled,
+                    Tag::Gradient(stops) => {
+                        let gradient = Gradient::from_colors(stops).with_space(ColorSpace::Srgb);
+                        inner = Some(gradient.take(gradient_len(children)).into_iter());
+                    }
+                    Tag::Rainbow(reversed, phase) => {
+                        let n = gradient_len(children);
+                        let mut hues: Vec<Color> = (0..n)
+                            .map(|i| {
+                                let turn = i as f32 / n as f32 + *phase as f32 / 10.0;
+                                let hue = (360.0 * turn).rem_euclid(360.0);
+                                Color::from_hsv(hue, 100.0, 100.0).unwrap()
+                            })
+                            .collect();
+                        if *reversed {
+                            hues.reverse();
+                        }
+                        inner = Some(hues.into_iter());
+                    }
+                }
+                if inner.is_some() || matches!(tag, Tag::Color(_)) {
+                    // Explicit colors take over from an enclosing gradient
+                    flatten(children, &style, &mut inner, text);
+                } else {
+                    flatten(children, &style, colors, text);
+                }
+            }
+        }
+    }
+}
+
+/// The MiniMessage tag for a color
+fn color_tag(color: &Color) -> String {
+    color_name(color).map_or_else(|| color.to_hex().to_ascii_lowercase(), str::to_string)
+}
+
+impl Text {
+    /// Parse MiniMessage such as `"<red>Hello <bold><#ff5733>world"`
+    ///
+    /// Supports colors (`<red>`, `<#ff5733>`, `<color:...>`), decorations and
+    /// their negations (`<bold>`, `<!italic>`), `<gradient:...>`,
+    /// `<rainbow:[!][phase]>` and `<reset>`. Gradients interpolate in sRGB
+    /// like Adventure; a gradient phase is accepted but ignored. Unknown or
+    /// malformed tags are kept as text, and `\<` escapes a tag.
+    pub fn from_minimessage(input: &str) -> Text {
+        let mut text = Text::new();
+        flatten(&parse_tree(input), &Style::default(), &mut None, &mut text);
+        text
+    }
+
+    /// Serialize to MiniMessage, opening and closing tags as the style changes
+    pub fn to_minimessage(&self) -> String {
+        let mut result = String::new();
+        // Open tags as (tag, canonical name), with the color first
+        let mut open: Vec<(String, &str)> = Vec::new();
+        let mut current = Style::default();
+
+        for span in &self.spans {
+            let style = &span.style;
+            let flags = [
+                ("bold", current.bold, style.bold),
+                ("italic", current.italic, style.italic),
+                ("underlined", current.underlined, style.underlined),
+                ("strikethrough", current.strikethrough, style.strikethrough),
+                ("obfuscated", current.obfuscated, style.obfuscated),
+            ];
+
+            // Close back to the last tag that still applies
+            let keep = if style.color != current.color {
+                0
+            } else {
+                open.iter()
+                    .position(|(_, name)| {
+                        flags
+                            .iter()
+                            .any(|(flag, was, now)| flag == name && was != now)
+                    })
+                    .unwrap_or(open.len())
+            };
+            for (tag, _) in open.drain(keep..).rev() {
+                result.push_str(&format!("</{}>", tag));
+            }
+
+            if keep == 0
+                && let Some(color) = &style.color
+            {
+                let tag = color_tag(color);
+                result.push_str(&format!("<{}>", tag));
+                open.push((tag, "color"));
+            }
+            for (name, _, enabled) in flags {
+                if enabled && !open.iter().any(|(_, open)| *open == name) {
+                    result.push_str(&format!("<{}>", name));
+                    open.push((name.to_string(), name));
+                }
+            }
+
+            for c in span.text.chars() {
+                if c == '<' || c == '\\' {
+                    result.push('\\');
+                }
+                result.push(c);
+            }
+            current = style.clone();
+        }
+
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::HexFormat;
+
+    #[test]
+    fn test_from_minimessage() {
+        let text =
+            Text::from_minimessage("<red>Hello <b><#FF5733>world</b>!<reset> \\<tag> <unknown>");
+        assert_eq!(
+            text.to_legacy(HexFormat::Ampersand),
+            "&cHello &#FF5733&lworld&c!&r <tag> <unknown>"
+        );
+
+        let text = Text::from_minimessage("<bold>A<!bold>B</bold>C</bold>");
+        assert_eq!(text.to_legacy(HexFormat::Ampersand), "&lA&rB&lC");
+    }
+
+    #[test]
+    fn test_gradient_and_rainbow() {
+        let text = Text::from_minimessage("<gradient:#ff0000:#0000ff>abc</gradient>d");
+        assert_eq!(
+            text.to_legacy(HexFormat::Ampersand),
+            "&#FF0000a&#800080b&#0000FFc&rd"
+        );
+
+        let text = Text::from_minimessage("<rainbow:!>ab<white>c</white></rainbow>");
+        assert_eq!(
+            text.to_legacy(HexFormat::Ampersand),
+            "&#00FFFFa&#FF0000b&fc"
+        );
+
+        // Only a trailing number is a gradient phase
+        let text = Text::from_minimessage("<gradient:#ff0000:#0000ff:0.5>ab");
+        assert_eq!(text.to_legacy(HexFormat::Ampersand), "&#FF0000a&#0000FFb");
+        assert_eq!(
+            Text::from_minimessage("<gradient:1:#ff0000:#0000ff>a").plain(),
+            "<gradient:1:#ff0000:#0000ff>a"
+        );
+
+        // A phase of 5 starts half way around the color wheel
+        let text = Text::from_minimessage("<rainbow:5>ab</rainbow>");
+        assert_eq!(text.to_legacy(HexFormat::Ampersand), "&#00FFFFa&#FF0000b");
+        let text = Text::from_minimessage("<rainbow:!2>a</rainbow>");
+        assert_eq!(text.spans[0].style.color, Color::from_hex("#CCFF00").ok());
+        assert_eq!(
+            Text::from_minimessage("<rainbow:x>a").plain(),
+            "<rainbow:x>a"
+        );
+    }
+
+    #[test]
+    fn test_to_minimessage() {
+        let text = Text::from_legacy("&c&lHello &#FF5733world&r<3");
+        let minimessage = text.to_minimessage();
+        assert_eq!(
+            minimessage,
+            "<red><bold>Hello </bold></red><#ff5733>world</#ff5733>\\<3"
+        );
+        assert_eq!(Text::from_minimessage(&minimessage), text);
+
+        let text = Text::from_legacy("&6&l&oA&6&lB");
+        assert_eq!(text.to_minimessage(), "<gold><bold><italic>A</italic>B");
+    }
+}
@@ -0,0 +1,203 @@
+//! Minecraft colors: text formatting, dyes, map art, commands and biome tints
+
+mod biome;
+mod command;
+mod dye;
+mod gradient_text;
+mod json;
+mod leather;
+mod legacy;
+mod map;
+mod minimessage;
+mod motd;
+mod nbt;
+mod width;
+
+pub use biome::{Biome, COLORMAP_SIZE, Colormap, GrassModifier};
+pub use command::{
+    DATA_VERSION_1_20_5, DATA_VERSION_1_21_4, DATA_VERSION_1_21_5, give_dyed_item, give_potion,
+    particle_dust, summon_firework,
+};
+pub use dye::{DyeColor, DyedBlock};
+pub use gradient_text::{gradient_text, rainbow_text};
+pub use leather::{LeatherRecipe, MAX_DYES_PER_CRAFT, dye_leather, leather_recipe};
+pub use legacy::{
+    MalformedCode, MalformedCodeKind, downsample_legacy_codes, strip_legacy_codes,
+    translate_legacy_codes, validate_legacy_codes,
+};
+pub use map::{MAP_SIZE, MapArtOptions, MapData, map_art, map_color, map_palette};
+pub use motd::Motd;
+pub use width::{TextArea, center_legacy, legacy_width, pad_legacy, wrap_legacy};
+
+use crate::Color;
+
+impl Color {
+    /// A color from a packed `0xRRGGBB` integer, for const tables
+    pub(crate) const fn from_packed(rgb: u32) -> Color {
+        Color {
+            r: (rgb >> 16) as u8,
+            g: (rgb >> 8) as u8,
+            b: rgb as u8,
+            a: 1.0,
+        }
+    }
+}
+
+/// The Minecraft edition whose color codes are used
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Edition {
+    /// Java Edition: codes `0` to `f` plus hex colors
+    #[default]
+    Java,
+    /// Bedrock Edition: codes `0` to `f`, `g` and the material codes
+    Bedrock,
+}
+
+/// Bedrock's codes beyond the shared 16 colors
+const BEDROCK_COLORS: [(char, Color); 12] = [
+    ('g', Color::MC_MINECOIN_GOLD),
+    ('h', Color::MC_MATERIAL_QUARTZ),
+    ('i', Color::MC_MATERIAL_IRON),
+    ('j', Color::MC_MATERIAL_NETHERITE),
+    ('m', Color::MC_MATERIAL_REDSTONE),
+    ('n', Color::MC_MATERIAL_COPPER),
+    ('p', Color::MC_MATERIAL_GOLD),
+    ('q', Color::MC_MATERIAL_EMERALD),
+    ('s', Color::MC_MATERIAL_DIAMOND),
+    ('t', Color::MC_MATERIAL_LAPIS),
+    ('u', Color::MC_MATERIAL_AMETHYST),
+    ('v', Color::MC_MATERIAL_RESIN),
+];
+
+impl Edition {
+    /// The color codes this edition has beyond `0` to `f`
+    pub(crate) fn extra_colors(self) -> &'static [(char, Color)] {
+        match self {
+            Edition::Java => &[],
+            Edition::Bedrock => &BEDROCK_COLORS,
+        }
+    }
+
+    /// The color of a code beyond `0` to `f`, in either case
+    pub(crate) fn extra_color(self, code: char) -> Option<Color> {
+        let code = code.to_ascii_lowercase();
+        self.extra_colors()
+            .iter()
+            .find(|(c, _)| *c == code)
+            .map(|(_, color)| color.clone())
+    }
+}
+
+/// How per-character hex colors are written
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum HexFormat {
+    /// `&#RRGGBB`, as used by most plugins
+    #[default]
+    Ampersand,
+    /// `&x&R&R&G&G&B&B`, as used by Spigot-style translators
+    AmpersandX,
+    /// `§x§R§R§G§G§B§B`, as understood by the vanilla client
+    SectionX,
+}
+
+impl HexFormat {
+    /// The color code for `color` in this format
+    pub fn code(self, color: &Color) -> String {
+        match self {
+            HexFormat::Ampersand => color.to_minecraft_hex(),
+            HexFormat::AmpersandX => color.to_minecraft_hex_alt(),
+            HexFormat::SectionX => color.to_minecraft_hex_alt().replace('&', "§"),
+        }
+    }
+
+    /// The prefix character used for formatting codes
+    pub fn prefix(self) -> char {
+        match self {
+            HexFormat::Ampersand | HexFormat::AmpersandX => '&',
+            HexFormat::SectionX => '§',
+        }
+    }
+}
+
+/// Whether `c` is a legacy formatting code (`k` to `o`)
+pub(crate) fn is_format_code(c: char) -> bool {
+    matches!(c.to_ascii_lowercase(), 'k'..='o')
+}
+
+/// Whether `c` is a legacy color code (`0` to `9`, `a` to `f`)
+pub(crate) fn is_color_code(c: char) -> bool {
+    c.is_ascii_hexdigit()
+}
+
+/// The 16 legacy colors by code and name
+const LEGACY_COLORS: [(char, &str, Color); 16] = [
+    ('0', "black", Color::MC_BLACK),
+    ('1', "dark_blue", Color::MC_DARK_BLUE),
+    ('2', "dark_green", Color::MC_DARK_GREEN),
+    ('3', "dark_aqua", Color::MC_DARK_AQUA),
+    ('4', "dark_red", Color::MC_DARK_RED),
+    ('5', "dark_purple", Color::MC_DARK_PURPLE),
+    ('6', "gold", Color::MC_GOLD),
+    ('7', "gray", Color::MC_GRAY),
+    ('8', "dark_gray", Color::MC_DARK_GRAY),
+    ('9', "blue", Color::MC_BLUE),
+    ('a', "green", Color::MC_GREEN),
+    ('b', "aqua", Color::MC_AQUA),
+    ('c', "red", Color::MC_RED),
+    ('d', "light_purple", Color::MC_LIGHT_PURPLE),
+    ('e', "yellow", Color::MC_YELLOW),
+    ('f', "white", Color::MC_WHITE),
+];
+
+/// The color of a legacy color code
+pub(crate) fn legacy_color(code: char) -> Option<Color> {
+    let code = code.to_ascii_lowercase();
+    LEGACY_COLORS
+        .iter()
+        .find(|(c, _, _)| *c == code)
+        .map(|(_, _, color)| color.clone())
+}
+
+/// The legacy color code of a color, if it is exactly one of the 16
+pub(crate) fn legacy_code(color: &Color) -> Option<char> {
+    LEGACY_COLORS
+        .iter()
+        .find(|(_, _, c)| (c.r, c.g, c.b) == (color.r, color.g, color.b))
+        .map(|(code, _, _)| *code)
+}
+
+/// The color of a named text component color such as `"dark_aqua"`
+pub(crate) fn named_color(name: &str) -> Option<Color> {
+    LEGACY_COLORS
+        .iter()
+        .find(|(_, n, _)| *n == name)
+        .map(|(_, _, color)| color.clone())
+}
+
+/// The text component name of a color, if it is exactly one of the 16
+pub(crate) fn color_name(color: &Color) -> Option<&'static str> {
+    let code = legacy_code(color)?;
+    LEGACY_COLORS
+        .iter()
+        .find(|(c, _, _)| *c == code)
+        .map(|(_, name, _)| *name)
+}
+
+/// Length of the hex color code starting at `i` (`&#RRGGBB` or `&x&R&R&G&G&B&B`)
+pub(crate) fn hex_code_len(chars: &[char], i: usize) -> Option<usize> {
+    let prefix = chars[i];
+    match chars.get(i + 1)? {
+        '#' => {
+            let digits = chars.get(i + 2..i + 8)?;
+            digits.iter().all(char::is_ascii_hexdigit).then_some(8)
+        }
+        'x' | 'X' => {
+            let pairs = chars.get(i + 2..i + 14)?;
+            pairs
+                .chunks(2)
+                .all(|pair| pair[0] == prefix && pair[1].is_ascii_hexdigit())
+                .then_some(14)
+        }
+        _ => None,
+    }
+}
@@ -0,0 +1,121 @@
+//! Server list MOTDs from Server List Ping responses
+
+use super::Edition;
+use super::json::text_from_value;
+use super::width::TextArea;
+use crate::json::Json;
+use crate::{ColorError, Text};
+
+/// A server list MOTD: the two lines shown under the server name
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Motd {
+    pub lines: Vec<Text>, // At most two lines
+}
+
+impl Motd {
+    /// Lay out text as a MOTD, wrapped to the MOTD width and keeping the
+    /// first two lines
+    pub fn from_text(text: &Text) -> Motd {
+        let mut lines = text.wrapped(TextArea::Motd);
+        lines.truncate(2);
+        Motd { lines }
+    }
+
+    /// Parse a MOTD from a Server List Ping status response
+    pub fn from_status_json(json: &str) -> Result<Motd, ColorError> {
+        let json = Json::parse(json).ok_or(ColorError::InvalidTextComponent)?;
+        let description = json
+            .get("description")
+            .ok_or(ColorError::InvalidTextComponent)?;
+        Motd::from_description(description)
+    }
+
+    /// Parse a MOTD from the `description` field of a status response alone
+    ///
+    /// Descriptions may be legacy strings or text components, and `§` codes
+    /// inside component text are applied as the vanilla client does.
+    pub fn from_description_json(json: &str) -> Result<Motd, ColorError> {
+        let json = Json::parse(json).ok_or(ColorError::InvalidTextComponent)?;
+        Motd::from_description(&json)
+    }
+
+    fn from_description(description: &Json) -> Result<Motd, ColorError> {
+        let components = text_from_value(description)?;
+        let mut text = Text::new();
+        for span in &components.spans {
+            text.push_legacy(&span.text, &span.style, &['§'], Edition::Java);
+        }
+        Ok(Motd::from_text(&text))
+    }
+
+    /// Width of each line in pixels
+    pub fn line_widths(&self) -> Vec<u32> {
+        self.lines.iter().map(Text::width).collect()
+    }
+
+    /// Center each line by prefixing spaces, as MOTD generators do
+    pub fn centered(&self) -> Motd {
+        let lines = self
+            .lines
+            .iter()
+            .map(|line| line.centered(TextArea::Motd))
+            .collect();
+        Motd { lines }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Color, HexFormat};
+
+    #[test]
+    fn test_legacy_description() {
+        let motd = Motd::from_status_json(include_str!("../../tests/fixtures/status_legacy.json"))
+            .unwrap();
+        assert_eq!(motd.lines.len(), 2);
+        assert_eq!(motd.lines[0].plain(), "Golden Realm - Survival");
+        assert_eq!(motd.lines[0].spans[0].style.color, Some(Color::MC_GOLD));
+        assert!(motd.lines[0].spans[0].style.bold);
+        assert_eq!(
+            motd.lines[1].to_legacy(HexFormat::SectionX),
+            "§eNew season is live!"
+        );
+    }
+
+    #[test]
+    fn test_component_description() {
+        let motd =
+            Motd::from_status_json(include_str!("../../tests/fixtures/status_component.json"))
+                .unwrap();
+        assert_eq!(
+            motd.lines[0].to_legacy(HexFormat::SectionX),
+            "§b§lSkyblock §x§F§F§5§7§3§3Network"
+        );
+        // `§` codes inside component text apply on top of the component style
+        assert_eq!(
+            motd.lines[1].to_legacy(HexFormat::SectionX),
+            "§7Join us at §nplay.example.net"
+        );
+
+        assert_eq!(
+            Motd::from_status_json("{\"players\":{}}"),
+            Err(ColorError::InvalidTextComponent)
+        );
+    }
+
+    #[test]
+    fn test_centered() {
+        let motd = Motd::from_description_json("\"§lHi\\n§rWelcome\"").unwrap();
+        assert_eq!(motd.line_widths(), [7 + 3, 6 * 6 + 3]);
+
+        let centered = motd.centered();
+        // (270 - 10) / 2 / 4 = 32 spaces, (270 - 39) / 2 / 4 = 28 spaces
+        assert_eq!(centered.lines[0].plain(), format!("{}Hi", " ".repeat(32)));
+        assert_eq!(
+            centered.lines[1].plain(),
+            format!("{}Welcome", " ".repeat(28))
+        );
+        assert!(centered.lines[0].spans[1].style.bold);
+    }
+}
@@ -0,0 +1,235 @@
+//! A minimal NBT writer and the gzip wrapping Minecraft expects
+
+/// An NBT tag, covering the types map data needs
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Tag {
+    Byte(i8),
+    Int(i32),
+    ByteArray(Vec<u8>),
+    String(String),
+    List(Vec<Tag>),
+    Compound(Vec<(String, Tag)>),
+}
+
+impl Tag {
+    fn id(&self) -> u8 {
+        match self {
+            Tag::Byte(_) => 1,
+            Tag::Int(_) => 3,
+            Tag::ByteArray(_) => 7,
+            Tag::String(_) => 8,
+            Tag::List(_) => 9,
+            Tag::Compound(_) => 10,
+        }
+    }
+
+    /// The tag as a named root tag, as stored in `.dat` files
+    pub(crate) fn to_bytes(&self, name: &str) -> Vec<u8> {
+        let mut out = vec![self.id()];
+        write_string(&mut out, name);
+        self.write_payload(&mut out);
+        out
+    }
+
+    fn write_payload(&self, out: &mut Vec<u8>) {
+        match self {
+            Tag::Byte(value) => out.push(*value as u8),
+            Tag::Int(value) => out.extend(value.to_be_bytes()),
+            Tag::ByteArray(bytes) => {
+                out.extend((bytes.len() as i32).to_be_bytes());
+                out.extend(bytes);
+            }
+            Tag::String(value) => write_string(out, value),
+            Tag::List(items) => {
+                // Empty lists are written with the End type, as vanilla does
+                out.push(items.first().map_or(0, Tag::id));
+                out.extend((items.len() as i32).to_be_bytes());
+                for item in items {
+                    item.write_payload(out);
+                }
+            }
+            Tag::Compound(entries) => {
+                for (name, tag) in entries {
+                    out.push(tag.id());
+                    write_string(out, name);
+                    tag.write_payload(out);
+                }
+                out.push(0);
+            }
+        }
+    }
+}
+
+/// A length-prefixed string; plain UTF-8 matches Java's modified UTF-8 for
+/// the ASCII names and IDs written here
+fn write_string(out: &mut Vec<u8>, value: &str) {
+    out.extend((value.len() as u16).to_be_bytes());
+    out.extend(value.as_bytes());
+}
+
+/// The CRC-32 lookup table (IEEE polynomial)
+const CRC_TABLE: [u32; 256] = {
+    let mut table = [0; 256];
+    let mut n = 0;
+    while n < 256 {
+        let mut c = n as u32;
+        let mut k = 0;
+        while k < 8 {
+            c = if c & 1 != 0 {
+                0xEDB88320 ^ (c >> 1)
+            } else {
+                c >> 1
+            };
+            k += 1;
+        }
+        table[n] = c;
+        n += 1;
+    }
+    table
+};
+
+fn crc32(data: &[u8]) -> u32 {
+    !data.iter().fold(!0, |crc, &byte| {
+        CRC_TABLE[((crc ^ byte as u32) & 0xFF) as usize] ^ (crc >> 8)
+    })
+}
+
+/// Wrap data in a gzip stream of uncompressed (stored) deflate blocks
+pub(crate) fn gzip(data: &[u8]) -> Vec<u8> {
+    let mut out = vec![0x1F, 0x8B, 8, 0, 0, 0, 0, 0, 0, 0xFF];
+    let mut blocks = data.chunks(0xFFFF).peekable();
+    if blocks.peek().is_none() {
+        out.extend([1, 0, 0, 0xFF, 0xFF]);
+    }
+    while let Some(block) = blocks.next() {
+        out.push(u8::from(blocks.peek().is_none()));
+        let len = block.len() as u16;
+        out.extend(len.to_le_bytes());
+        out.extend((!len).to_le_bytes());
+        out.extend(block);
+    }
+    out.extend(crc32(data).to_le_bytes());
+    out.extend((data.len() as u32).to_le_bytes());
+    out
+}
+
+/// Readers for the output above, to check round trips
+#[cfg(test)]
+pub(crate) mod read {
+    use super::{Tag, crc32};
+
+    /// Unwrap a gzip stream made of stored blocks
+    pub(crate) fn gunzip(data: &[u8]) -> Option<Vec<u8>> {
+        if data.get(..3)? != [0x1F, 0x8B, 8] {
+            return None;
+        }
+        let mut out = Vec::new();
+        let mut pos = 10;
+        loop {
+            let header = *data.get(pos)?;
+            if header & 0b110 != 0 {
+                return None; // Compressed blocks are not supported
+            }
+            let len = u16::from_le_bytes([data[pos + 1], data[pos + 2]]) as usize;
+            out.extend(data.get(pos + 5..pos + 5 + len)?);
+            pos += 5 + len;
+            if header & 1 == 1 {
+                break;
+            }
+        }
+        let crc = u32::from_le_bytes(data.get(pos..pos + 4)?.try_into().ok()?);
+        (crc == crc32(&out)).then_some(out)
+    }
+
+    /// Parse a named root tag
+    pub(crate) fn parse(data: &[u8]) -> Option<(String, Tag)> {
+        let mut reader = Reader { data, pos: 0 };
+        let id = reader.take(1)?[0];
+        let name = reader.string()?;
+        Some((name, reader.payload(id)?))
+    }
+
+    struct Reader<'a> {
+        data: &'a [u8],
+        pos: usize,
+    }
+
+    impl Reader<'_> {
+        fn take(&mut self, n: usize) -> Option<&[u8]> {
+            let bytes = self.data.get(self.pos..self.pos + n)?;
+            self.pos += n;
+            Some(bytes)
+        }
+
+        fn int(&mut self) -> Option<i32> {
+            Some(i32::from_be_bytes(self.take(4)?.try_into().ok()?))
+        }
+
+        fn string(&mut self) -> Option<String> {
+            let len = u16::from_be_bytes(self.take(2)?.try_into().ok()?) as usize;
+            String::from_utf8(self.take(len)?.to_vec()).ok()
+        }
+
+        fn payload(&mut self, id: u8) -> Option<Tag> {
+            let tag = match id {
+                1 => Tag::Byte(self.take(1)?[0] as i8),
+                3 => Tag::Int(self.int()?),
+                7 => {
+                    let len = self.int()? as usize;
+                    Tag::ByteArray(self.take(len)?.to_vec())
+                }
+                8 => Tag::String(self.string()?),
+                9 => {
+                    let item_id = self.take(1)?[0];
+                    let len = self.int()?;
+                    let items = (0..len).map(|_| self.payload(item_id));
+                    Tag::List(items.collect::<Option<_>>()?)
+                }
+                10 => {
+                    let mut entries = Vec::new();
+                    loop {
+                        let id = self.take(1)?[0];
+                        if id == 0 {
+                            break;
+                        }
+                        let name = self.string()?;
+                        entries.push((name, self.payload(id)?));
+                    }
+                    Tag::Compound(entries)
+                }
+                _ => return None,
+            };
+            Some(tag)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_crc32() {
+        assert_eq!(crc32(b"123456789"), 0xCBF43926);
+    }
+
+    #[test]
+    fn test_nbt_round_trip() {
+        let tag = Tag::Compound(vec![
+            ("name".to_string(), Tag::String("map".to_string())),
+            (
+                "list".to_string(),
+                Tag::List(vec![Tag::Int(1), Tag::Int(-2)]),
+            ),
+            ("empty".to_string(), Tag::List(Vec::new())),
+            ("bytes".to_string(), Tag::ByteArray(vec![0; 70000])),
+        ]);
+        let bytes = tag.to_bytes("");
+        assert_eq!(&bytes[..3], [10, 0, 0]);
+
+        let unzipped = read::gunzip(&gzip(&bytes)).unwrap();
+        assert_eq!(unzipped, bytes);
+        assert_eq!(read::parse(&unzipped), Some((String::new(), tag)));
+        assert_eq!(read::gunzip(&gzip(&[])), Some(Vec::new()));
+    }
+}
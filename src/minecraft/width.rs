@@ -0,0 +1,281 @@
+//! Vanilla default font glyph widths, centering and wrapping
+
+use super::Edition;
+use super::legacy::{LegacyToken, legacy_tokens};
+use crate::{Span, Style, Text};
+
+/// Advance of a space in pixels
+pub(crate) const SPACE_WIDTH: u32 = 4;
+
+/// Where text is shown, which determines how wide a line may be
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TextArea {
+    /// The chat box at default settings (320px)
+    Chat,
+    /// A book page (114px)
+    Book,
+    /// A sign line (90px)
+    Sign,
+    /// A server list MOTD line (270px)
+    Motd,
+    /// Any other width in pixels
+    Custom(u32),
+}
+
+impl TextArea {
+    /// The line width in pixels
+    pub fn width(self) -> u32 {
+        match self {
+            TextArea::Chat => 320,
+            TextArea::Book => 114,
+            TextArea::Sign => 90,
+            TextArea::Motd => 270,
+            TextArea::Custom(width) => width,
+        }
+    }
+}
+
+/// Advance of a character in the default font in pixels, including the
+/// 1px gap after it; bold adds a pixel to everything but spaces
+pub(crate) fn glyph_width(c: char, bold: bool) -> u32 {
+    let width = match c {
+        ' ' => return SPACE_WIDTH,
+        '!' | '\'' | ',' | '.' | ':' | ';' | 'i' | '|' => 2,
+        '`' | 'l' => 3,
+        'I' | '[' | ']' | 't' | '"' => 4,
+        '(' | ')' | '*' | '<' | '>' | 'f' | 'k' | '{' | '}' => 5,
+        '@' | '~' => 7,
+        _ => 6,
+    };
+    width + u32::from(bold)
+}
+
+/// Spaces that center content of `width` pixels in `area`
+fn centering_spaces(width: u32, area: TextArea) -> String {
+    let free = area.width().saturating_sub(width);
+    " ".repeat((free / 2 / SPACE_WIDTH) as usize)
+}
+
+/// Spaces that fill the rest of `area` after content of `width` pixels
+fn padding_spaces(width: u32, area: TextArea) -> String {
+    let free = area.width().saturating_sub(width);
+    " ".repeat((free / SPACE_WIDTH) as usize)
+}
+
+/// Split characters into lines like vanilla's text splitter
+///
+/// Lines break at `\n` and, when too wide, after the last space (which is
+/// dropped) or else before the character that overflows. Returns the index
+/// ranges of the characters on each line.
+fn break_lines(chars: &[(char, u32)], max_width: u32) -> Vec<(usize, usize)> {
+    let mut lines = Vec::new();
+    let mut start = 0;
+    let mut width = 0;
+    let mut last_space = None;
+    let mut i = 0;
+
+    while i < chars.len() {
+        let (c, advance) = chars[i];
+        if c == '\n' {
+            lines.push((start, i));
+            (start, width, last_space) = (i + 1, 0, None);
+        } else if width + advance > max_width && i > start {
+            match last_space {
+                Some(space) => {
+                    lines.push((start, space));
+                    start = space + 1;
+                }
+                None => {
+                    lines.push((start, i));
+                    start = i;
+                }
+            }
+            (width, last_space) = (0, None);
+            i = start;
+            continue;
+        } else {
+            if c == ' ' {
+                last_space = Some(i);
+            }
+            width += advance;
+        }
+        i += 1;
+    }
+
+    lines.push((start, chars.len()));
+    lines
+}
+
+impl Text {
+    /// Width in pixels in the vanilla default font
+    pub fn width(&self) -> u32 {
+        self.spans
+            .iter()
+            .flat_map(|span| span.text.chars().map(|c| glyph_width(c, span.style.bold)))
+            .sum()
+    }
+
+    /// Prefix spaces so the text appears centered in `area`
+    pub fn centered(&self, area: TextArea) -> Text {
+        let mut result = Text::new();
+        result.push(&centering_spaces(self.width(), area), &Style::default());
+        for span in &self.spans {
+            result.push(&span.text, &span.style);
+        }
+        result
+    }
+
+    /// Append unstyled spaces so the text fills `area`
+    pub fn padded(&self, area: TextArea) -> Text {
+        let mut result = self.clone();
+        result.push(&padding_spaces(self.width(), area), &Style::default());
+        result
+    }
+
+    /// Split into lines that fit `area`, breaking at spaces where possible
+    pub fn wrapped(&self, area: TextArea) -> Vec<Text> {
+        let styled: Vec<(char, &Style)> = self
+            .spans
+            .iter()
+            .flat_map(|span: &Span| span.text.chars().map(move |c| (c, &span.style)))
+            .collect();
+        let widths: Vec<(char, u32)> = styled
+            .iter()
+            .map(|&(c, style)| (c, glyph_width(c, style.bold)))
+            .collect();
+
+        break_lines(&widths, area.width())
+            .into_iter()
+            .map(|(start, end)| {
+                let mut line = Text::new();
+                for &(c, style) in &styled[start..end] {
+                    line.push(c.encode_utf8(&mut [0; 4]), style);
+                }
+                line
+            })
+            .collect()
+    }
+}
+
+/// Width in pixels of a string with `§`/`&` legacy and hex codes
+pub fn legacy_width(text: &str) -> u32 {
+    Text::from_legacy(text).width()
+}
+
+/// Prefix spaces so a legacy formatted string appears centered in `area`
+pub fn center_legacy(text: &str, area: TextArea) -> String {
+    centering_spaces(legacy_width(text), area) + text
+}
+
+/// Append spaces so a legacy formatted string fills `area`
+///
+/// The spaces take the style active at the end of the string.
+pub fn pad_legacy(text: &str, area: TextArea) -> String {
+    text.to_string() + &padding_spaces(legacy_width(text), area)
+}
+
+/// Split a legacy formatted string into lines that fit `area`
+///
+/// Each line starts with the codes still active from the lines before, in
+/// the form they were written.
+pub fn wrap_legacy(text: &str, area: TextArea) -> Vec<String> {
+    let tokens = legacy_tokens(text, &['&', '§'], Edition::Java);
+    let mut style = Style::default();
+    // Visible characters with their advance and token index
+    let mut chars = Vec::new();
+    let mut positions = Vec::new();
+    for (i, token) in tokens.iter().enumerate() {
+        match token {
+            LegacyToken::Char(c) => {
+                chars.push((*c, glyph_width(*c, style.bold)));
+                positions.push(i);
+            }
+            code => code.apply(&mut style, &Style::default()),
+        }
+    }
+
+    // The codes in effect before token `end`, as written
+    let active_codes = |end: usize| {
+        let mut codes: Vec<&str> = Vec::new();
+        for token in &tokens[..end] {
+            match token {
+                LegacyToken::Char(_) => {}
+                LegacyToken::Color(_, raw) => codes = vec![raw],
+                LegacyToken::Format(_, raw) => codes.push(raw),
+                LegacyToken::Reset(_) => codes.clear(),
+            }
+        }
+        codes.concat()
+    };
+    let raw = |token: &LegacyToken| match token {
+        LegacyToken::Char(c) => c.to_string(),
+        LegacyToken::Color(_, raw) | LegacyToken::Format(_, raw) | LegacyToken::Reset(raw) => {
+            raw.to_string()
+        }
+    };
+
+    let lines = break_lines(&chars, area.width());
+    let last_line = lines.len() - 1;
+    let mut result = Vec::with_capacity(lines.len());
+    for (index, (start, end)) in lines.into_iter().enumerate() {
+        // Codes between lines are carried over by `active_codes`, and the
+        // last line keeps any trailing codes
+        let first = positions.get(start).copied().unwrap_or(tokens.len());
+        let last = match end {
+            _ if index == last_line => tokens.len(),
+            end if end > start => positions[end - 1] + 1,
+            _ => first,
+        };
+        let mut line = active_codes(first);
+        line.extend(tokens[first..last].iter().map(raw));
+        result.push(line);
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_width() {
+        assert_eq!(legacy_width("Hi!"), 6 + 2 + 2);
+        assert_eq!(legacy_width("&lHi &r&#FF5733x"), 7 + 3 + 4 + 6);
+        assert_eq!(legacy_width("§x§F§F§5§7§3§3ll"), 6);
+        assert_eq!(TextArea::Custom(50).width(), 50);
+    }
+
+    #[test]
+    fn test_center_and_pad() {
+        // 320 - 10 = 310 free, 155 / 4 = 38 spaces
+        assert_eq!(
+            center_legacy("&lHi", TextArea::Chat),
+            format!("{}&lHi", " ".repeat(38))
+        );
+        assert_eq!(
+            pad_legacy("&cHi", TextArea::Sign),
+            format!("&cHi{}", " ".repeat(20))
+        );
+
+        let text = Text::from_legacy("§lHi");
+        assert_eq!(text.centered(TextArea::Chat).width(), 38 * 4 + 10);
+        assert_eq!(text.padded(TextArea::Custom(20)).plain(), "Hi  ");
+    }
+
+    #[test]
+    fn test_wrap() {
+        // Bold "Hello " is 33px, and the hex color ends the bold
+        let lines = wrap_legacy("&c&lHello &#FF5733world again", TextArea::Custom(40));
+        assert_eq!(lines, ["&c&lHello", "&#FF5733world", "&#FF5733again"]);
+
+        let lines = wrap_legacy("abcdefghij\n&6x", TextArea::Custom(30));
+        assert_eq!(lines, ["abcde", "fghij", "&6x"]);
+
+        let text = Text::from_legacy("§lBold words wrap");
+        let lines = text.wrapped(TextArea::Custom(40));
+        assert_eq!(lines.len(), 3);
+        assert!(lines.iter().all(|line| line.width() <= 40));
+        assert!(lines[2].spans[0].style.bold);
+    }
+}
@@ -0,0 +1,244 @@
+//! CSS Color 5 `color-mix()` interpolation
+
+use crate::{Color, ColorError, ColorSpace};
+
+/// How hue angles are interpolated in polar color spaces
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum HueInterpolation {
+    /// Take the arc of at most 180 degrees
+    #[default]
+    Shorter,
+    /// Take the arc of at least 180 degrees
+    Longer,
+    /// Always move toward larger hue angles
+    Increasing,
+    /// Always move toward smaller hue angles
+    Decreasing,
+}
+
+impl HueInterpolation {
+    /// Adjust two hue angles so that linear interpolation follows this method
+    fn fixup(self, h1: f32, h2: f32) -> (f32, f32) {
+        let diff = h2 - h1;
+        match self {
+            HueInterpolation::Shorter if diff > 180.0 => (h1 + 360.0, h2),
+            HueInterpolation::Shorter if diff < -180.0 => (h1, h2 + 360.0),
+            HueInterpolation::Longer if diff > 0.0 && diff < 180.0 => (h1 + 360.0, h2),
+            HueInterpolation::Longer if diff > -180.0 && diff <= 0.0 => (h1, h2 + 360.0),
+            HueInterpolation::Increasing if h2 < h1 => (h1, h2 + 360.0),
+            HueInterpolation::Decreasing if h1 < h2 => (h1 + 360.0, h2),
+            _ => (h1, h2),
+        }
+    }
+}
+
+impl ColorSpace {
+    /// Interpolate between two colors with premultiplied alpha
+    ///
+    /// `t` is the weight of `to`. A missing (powerless) hue takes the other
+    /// color's hue, as CSS specifies for missing components.
+    pub(crate) fn interpolate(
+        self,
+        from: &Color,
+        to: &Color,
+        t: f32,
+        hue: HueInterpolation,
+    ) -> Color {
+        let mut a = self.components(from);
+        let mut b = self.components(to);
+        let hue_index = self.hue_index();
+
+        if let Some(i) = hue_index {
+            match (a[i].is_nan(), b[i].is_nan()) {
+                (true, false) => a[i] = b[i],
+                (false, true) => b[i] = a[i],
+                _ => {}
+            }
+            let h1 = a[i].rem_euclid(360.0);
+            let h2 = b[i].rem_euclid(360.0);
+            (a[i], b[i]) = hue.fixup(h1, h2);
+        }
+
+        for i in 0..3 {
+            if Some(i) != hue_index {
+                a[i] *= from.a;
+                b[i] *= to.a;
+            }
+        }
+
+        let lerp = |x: f32, y: f32| x + (y - x) * t;
+        let alpha = lerp(from.a, to.a);
+        let mut components = [lerp(a[0], b[0]), lerp(a[1], b[1]), lerp(a[2], b[2])];
+
+        for (i, c) in components.iter_mut().enumerate() {
+            if Some(i) == hue_index {
+                *c = c.rem_euclid(360.0);
+            } else if alpha > 0.0 {
+                *c /= alpha;
+            }
+        }
+
+        self.compose(components, alpha)
+    }
+}
+
+impl Color {
+    /// Mix with another color in the given space, like `color-mix()`
+    ///
+    /// `ratio` is the proportion of `other` (0.0 to 1.0) and hues take the
+    /// shorter arc.
+    pub fn mix(&self, other: &Color, ratio: f32, space: ColorSpace) -> Color {
+        space.interpolate(
+            self,
+            other,
+            ratio.clamp(0.0, 1.0),
+            HueInterpolation::Shorter,
+        )
+    }
+
+    /// CSS `color-mix(in <space> <hue> hue, self p1, other p2)`
+    ///
+    /// Percentages are normalized as CSS Color 5 specifies: an omitted
+    /// percentage is 100 minus the other (50 each if both are omitted), and
+    /// percentages summing to less than 100 scale down the result's alpha.
+    pub fn color_mix(
+        &self,
+        p1: Option<f32>,
+        other: &Color,
+        p2: Option<f32>,
+        space: ColorSpace,
+        hue: HueInterpolation,
+    ) -> Result<Color, ColorError> {
+        if [p1, p2]
+            .iter()
+            .flatten()
+            .any(|p| !(0.0..=100.0).contains(p))
+        {
+            return Err(ColorError::InvalidMixPercentage);
+        }
+
+        let (p1, p2) = match (p1, p2) {
+            (None, None) => (50.0, 50.0),
+            (Some(p1), None) => (p1, 100.0 - p1),
+            (None, Some(p2)) => (100.0 - p2, p2),
+            (Some(p1), Some(p2)) => (p1, p2),
+        };
+
+        let sum = p1 + p2;
+        if sum == 0.0 {
+            return Err(ColorError::InvalidMixPercentage);
+        }
+        let alpha_multiplier = if sum < 100.0 { sum / 100.0 } else { 1.0 };
+
+        let mut mixed = space.interpolate(self, other, p2 / sum, hue);
+        mixed.a *= alpha_multiplier;
+        Ok(mixed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SPACES: [ColorSpace; 9] = [
+        ColorSpace::Srgb,
+        ColorSpace::SrgbLinear,
+        ColorSpace::Xyz,
+        ColorSpace::Lab,
+        ColorSpace::Lch,
+        ColorSpace::Oklab,
+        ColorSpace::Oklch,
+        ColorSpace::Hsl,
+        ColorSpace::Hwb,
+    ];
+
+    #[test]
+    fn test_mix_endpoints() {
+        for space in SPACES {
+            assert_eq!(Color::RED.mix(&Color::BLUE, 0.0, space), Color::RED);
+            assert_eq!(Color::RED.mix(&Color::BLUE, 1.0, space), Color::BLUE);
+        }
+    }
+
+    #[test]
+    fn test_mix_spaces() {
+        let srgb = Color::RED.mix(&Color::BLUE, 0.5, ColorSpace::Srgb);
+        assert_eq!(srgb, Color::from_rgb(128, 0, 128));
+
+        let linear = Color::RED.mix(&Color::BLUE, 0.5, ColorSpace::SrgbLinear);
+        assert_eq!(linear, Color::from_rgb(188, 0, 188));
+
+        let hsl = Color::RED.mix(&Color::from_rgb(0, 255, 0), 0.5, ColorSpace::Hsl);
+        assert_eq!(hsl, Color::YELLOW);
+
+        // Gray has a powerless hue, so the other color's hue is used
+        let gray = Color::from_rgb(128, 128, 128);
+        let oklch = gray.mix(&Color::RED, 0.5, ColorSpace::Oklch);
+        assert!((oklch.to_oklch().h - Color::RED.to_oklch().h).abs() < 2.0);
+    }
+
+    #[test]
+    fn test_hue_interpolation_methods() {
+        let mix = |hue| {
+            Color::RED
+                .color_mix(None, &Color::BLUE, None, ColorSpace::Hsl, hue)
+                .unwrap()
+        };
+
+        // Red is at 0 degrees and blue at 240 degrees
+        assert_eq!(mix(HueInterpolation::Shorter), Color::MAGENTA);
+        assert_eq!(mix(HueInterpolation::Longer), Color::from_rgb(0, 255, 0));
+        assert_eq!(
+            mix(HueInterpolation::Increasing),
+            Color::from_rgb(0, 255, 0)
+        );
+        assert_eq!(mix(HueInterpolation::Decreasing), Color::MAGENTA);
+    }
+
+    #[test]
+    fn test_percentage_normalization() {
+        let mix = |p1, p2| {
+            Color::RED.color_mix(
+                p1,
+                &Color::BLUE,
+                p2,
+                ColorSpace::Srgb,
+                HueInterpolation::Shorter,
+            )
+        };
+
+        assert_eq!(mix(Some(25.0), None), mix(None, Some(75.0)));
+        assert_eq!(mix(Some(25.0), None).unwrap(), Color::from_rgb(64, 0, 191));
+
+        // Percentages over 100 are scaled down proportionally
+        assert_eq!(mix(Some(60.0), Some(90.0)), mix(Some(40.0), Some(60.0)));
+
+        // Percentages under 100 reduce the alpha
+        let faded = mix(Some(20.0), Some(20.0)).unwrap();
+        assert_eq!(faded, Color::from_rgba(128, 0, 128, 0.4));
+
+        assert_eq!(
+            mix(Some(0.0), Some(0.0)),
+            Err(ColorError::InvalidMixPercentage)
+        );
+        assert_eq!(
+            mix(Some(120.0), None),
+            Err(ColorError::InvalidMixPercentage)
+        );
+    }
+
+    #[test]
+    fn test_premultiplied_alpha() {
+        let transparent = Color::from_rgba(0, 0, 255, 0.0);
+        let mixed = Color::RED
+            .color_mix(
+                None,
+                &transparent,
+                None,
+                ColorSpace::Srgb,
+                HueInterpolation::Shorter,
+            )
+            .unwrap();
+        assert_eq!(mixed, Color::from_rgba(255, 0, 0, 0.5));
+    }
+}
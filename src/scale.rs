@@ -0,0 +1,142 @@
+//! Tint, shade and tone ramps generated from a single color
+
+use crate::{Color, ColorSpace, Easing, HueInterpolation};
+use std::collections::BTreeMap;
+
+/// Tailwind-style scale keys with their mix amount toward white (negative)
+/// or black (positive)
+const TAILWIND_STEPS: [(u16, f32); 11] = [
+    (50, -0.9),
+    (100, -0.8),
+    (200, -0.6),
+    (300, -0.4),
+    (400, -0.2),
+    (500, 0.0),
+    (600, 0.2),
+    (700, 0.4),
+    (800, 0.6),
+    (900, 0.8),
+    (950, 0.9),
+];
+
+/// Generates tints (toward white), shades (toward black) and tones (toward gray)
+#[derive(Debug, Clone, PartialEq)]
+pub struct Scale {
+    base: Color,
+    space: ColorSpace,
+    easing: Easing,
+}
+
+impl Scale {
+    /// Create a scale for a base color, interpolating linearly in Oklab
+    pub fn new(base: &Color) -> Self {
+        Scale {
+            base: base.clone(),
+            space: ColorSpace::Oklab,
+            easing: Easing::Linear,
+        }
+    }
+
+    /// Set the color space used for interpolation
+    pub fn with_space(mut self, space: ColorSpace) -> Self {
+        self.space = space;
+        self
+    }
+
+    /// Set the curve applied to the spacing of the steps
+    pub fn with_easing(mut self, easing: Easing) -> Self {
+        self.easing = easing;
+        self
+    }
+
+    /// `n` colors from the base color toward white (base first, white excluded)
+    pub fn tints(&self, n: usize) -> Vec<Color> {
+        self.ramp(&Color::WHITE, n)
+    }
+
+    /// `n` colors from the base color toward black (base first, black excluded)
+    pub fn shades(&self, n: usize) -> Vec<Color> {
+        self.ramp(&Color::BLACK, n)
+    }
+
+    /// `n` colors from the base color toward mid gray (base first, gray excluded)
+    pub fn tones(&self, n: usize) -> Vec<Color> {
+        self.ramp(&Color::from_rgb(128, 128, 128), n)
+    }
+
+    /// Tailwind-style map keyed 50, 100, ..., 900, 950 with the base color at 500
+    pub fn tailwind(&self) -> BTreeMap<u16, Color> {
+        TAILWIND_STEPS
+            .iter()
+            .map(|&(key, amount)| {
+                let target = if amount < 0.0 {
+                    &Color::WHITE
+                } else {
+                    &Color::BLACK
+                };
+                (key, self.step(target, amount.abs()))
+            })
+            .collect()
+    }
+
+    fn ramp(&self, target: &Color, n: usize) -> Vec<Color> {
+        (0..n)
+            .map(|i| self.step(target, i as f32 / n as f32))
+            .collect()
+    }
+
+    fn step(&self, target: &Color, amount: f32) -> Color {
+        self.space.interpolate(
+            &self.base,
+            target,
+            self.easing.apply(amount),
+            HueInterpolation::Shorter,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tints_and_shades() {
+        let base = Color::from_hex("#3B82F6").unwrap();
+        let scale = Scale::new(&base);
+
+        let tints = scale.tints(5);
+        assert_eq!(tints.len(), 5);
+        assert_eq!(tints[0], base);
+        assert!(
+            tints
+                .windows(2)
+                .all(|w| w[1].luminance() > w[0].luminance())
+        );
+
+        let shades = scale.shades(5);
+        assert_eq!(shades[0], base);
+        assert!(
+            shades
+                .windows(2)
+                .all(|w| w[1].luminance() < w[0].luminance())
+        );
+
+        let tones = scale.tones(4);
+        assert!(tones[3].to_hsl().s < base.to_hsl().s);
+    }
+
+    #[test]
+    fn test_tailwind_scale() {
+        let base = Color::from_hex("#3B82F6").unwrap();
+        let map = Scale::new(&base)
+            .with_space(ColorSpace::Oklch)
+            .with_easing(Easing::EaseOut)
+            .tailwind();
+
+        let keys: Vec<u16> = map.keys().copied().collect();
+        assert_eq!(keys, [50, 100, 200, 300, 400, 500, 600, 700, 800, 900, 950]);
+        assert_eq!(map[&500], base);
+        assert!(map[&50].luminance() > map[&400].luminance());
+        assert!(map[&950].luminance() < map[&600].luminance());
+    }
+}
@@ -0,0 +1,606 @@
+//! Perceptual color spaces (CIE XYZ, Lab, LCH, Oklab and Oklch)
+//!
+//! Matrices and constants follow the CSS Color Module Level 4 sample code:
+//! XYZ uses the D65 white point and Lab/LCH use D50, as in CSS.
+
+use crate::Color;
+use std::fmt;
+
+/// CIE XYZ color representation (D65 white point)
+#[derive(Debug, Clone, PartialEq)]
+pub struct Xyz {
+    pub x: f32, // 0.0 to ~0.95
+    pub y: f32, // 0.0 to 1.0
+    pub z: f32, // 0.0 to ~1.09
+}
+
+/// CIE Lab color representation (D50 white point)
+#[derive(Debug, Clone, PartialEq)]
+pub struct Lab {
+    pub l: f32, // 0.0 to 100.0
+    pub a: f32, // roughly -128.0 to 127.0
+    pub b: f32, // roughly -128.0 to 127.0
+}
+
+/// CIE LCH (Lightness, Chroma, Hue) color representation
+#[derive(Debug, Clone, PartialEq)]
+pub struct Lch {
+    pub l: f32, // 0.0 to 100.0
+    pub c: f32, // 0.0 to ~150.0
+    pub h: f32, // 0.0 to 360.0
+}
+
+/// Oklab color representation
+#[derive(Debug, Clone, PartialEq)]
+pub struct Oklab {
+    pub l: f32, // 0.0 to 1.0
+    pub a: f32, // roughly -0.4 to 0.4
+    pub b: f32, // roughly -0.4 to 0.4
+}
+
+/// Oklch (Lightness, Chroma, Hue) color representation
+#[derive(Debug, Clone, PartialEq)]
+pub struct Oklch {
+    pub l: f32, // 0.0 to 1.0
+    pub c: f32, // 0.0 to ~0.4
+    pub h: f32, // 0.0 to 360.0
+}
+
+/// Color space used by [`Color::darken_in`] and [`Color::lighten_in`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LightnessMode {
+    /// Scale gamma-encoded RGB toward black or white (same as `darken`/`lighten`)
+    Srgb,
+    /// Adjust CIE L* while keeping a* and b*
+    Lab,
+    /// Adjust Oklab L while keeping chroma and hue
+    Oklch,
+}
+
+/// Color space used for interpolation
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColorSpace {
+    /// Gamma-encoded sRGB
+    Srgb,
+    /// Linear-light sRGB
+    SrgbLinear,
+    /// CIE XYZ (D65)
+    Xyz,
+    /// CIE Lab (D50)
+    Lab,
+    /// CIE LCH (D50)
+    Lch,
+    /// Oklab
+    Oklab,
+    /// Oklch
+    Oklch,
+    /// HSL
+    Hsl,
+    /// HWB
+    Hwb,
+}
+
+const D50_WHITE: [f32; 3] = [0.964_295_7, 1.0, 0.825_104_6];
+const LAB_EPSILON: f32 = 216.0 / 24389.0;
+const LAB_KAPPA: f32 = 24389.0 / 27.0;
+
+/// Just-noticeable difference in Oklab used by gamut mapping
+const GAMUT_JND: f32 = 0.02;
+const GAMUT_EPSILON: f32 = 0.0001;
+
+fn mul3(m: &[[f32; 3]; 3], v: [f32; 3]) -> [f32; 3] {
+    [
+        m[0][0] * v[0] + m[0][1] * v[1] + m[0][2] * v[2],
+        m[1][0] * v[0] + m[1][1] * v[1] + m[1][2] * v[2],
+        m[2][0] * v[0] + m[2][1] * v[1] + m[2][2] * v[2],
+    ]
+}
+
+const LINEAR_SRGB_TO_XYZ: [[f32; 3]; 3] = [
+    [0.412_390_8, 0.357_584_33, 0.180_480_8],
+    [0.212_639, 0.715_168_65, 0.072_192_32],
+    [0.019_330_818, 0.119_194_78, 0.950_532_14],
+];
+
+const XYZ_TO_LINEAR_SRGB: [[f32; 3]; 3] = [
+    [3.240_97, -1.537_383_2, -0.498_610_76],
+    [-0.969_243_65, 1.875_967_5, 0.041_555_06],
+    [0.055_630_08, -0.203_976_96, 1.056_971_5],
+];
+
+const D65_TO_D50: [[f32; 3]; 3] = [
+    [1.047_929_8, 0.022_946_794, -0.050_192_23],
+    [0.029_627_815, 0.990_434_5, -0.017_073_825],
+    [-0.009_243_058, 0.015_055_145, 0.751_874_3],
+];
+
+const D50_TO_D65: [[f32; 3]; 3] = [
+    [0.955_473_4, -0.023_098_537, 0.063_259_31],
+    [-0.028_369_706, 1.009_995_5, 0.021_041_399],
+    [0.012_314_002, -0.020_507_697, 1.330_366],
+];
+
+pub(crate) fn srgb_to_linear(c: f32) -> f32 {
+    let abs = c.abs();
+    if abs <= 0.04045 {
+        c / 12.92
+    } else {
+        c.signum() * ((abs + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+pub(crate) fn linear_to_srgb(c: f32) -> f32 {
+    let abs = c.abs();
+    if abs <= 0.003_130_8 {
+        c * 12.92
+    } else {
+        c.signum() * (1.055 * abs.powf(1.0 / 2.4) - 0.055)
+    }
+}
+
+pub(crate) fn linear_to_oklab(rgb: [f32; 3]) -> [f32; 3] {
+    let [r, g, b] = rgb;
+    let l = (0.412_221_46 * r + 0.536_332_55 * g + 0.051_445_995 * b).cbrt();
+    let m = (0.211_903_5 * r + 0.680_699_5 * g + 0.107_396_96 * b).cbrt();
+    let s = (0.088_302_46 * r + 0.281_718_85 * g + 0.629_978_7 * b).cbrt();
+
+    [
+        0.210_454_26 * l + 0.793_617_8 * m - 0.004_072_047 * s,
+        1.977_998_5 * l - 2.428_592_2 * m + 0.450_593_7 * s,
+        0.025_904_037 * l + 0.782_771_77 * m - 0.808_675_77 * s,
+    ]
+}
+
+pub(crate) fn oklab_to_linear(lab: [f32; 3]) -> [f32; 3] {
+    let [l, a, b] = lab;
+    let l_ = (l + 0.396_337_78 * a + 0.215_803_76 * b).powi(3);
+    let m_ = (l - 0.105_561_346 * a - 0.063_854_17 * b).powi(3);
+    let s_ = (l - 0.089_484_18 * a - 1.291_485_5 * b).powi(3);
+
+    [
+        4.076_741_7 * l_ - 3.307_711_6 * m_ + 0.230_969_94 * s_,
+        -1.268_438 * l_ + 2.609_757_4 * m_ - 0.341_319_38 * s_,
+        -0.004_196_086_3 * l_ - 0.703_418_6 * m_ + 1.707_614_7 * s_,
+    ]
+}
+
+pub(crate) fn linear_to_xyz(rgb: [f32; 3]) -> [f32; 3] {
+    mul3(&LINEAR_SRGB_TO_XYZ, rgb)
+}
+
+pub(crate) fn xyz_to_linear(xyz: [f32; 3]) -> [f32; 3] {
+    mul3(&XYZ_TO_LINEAR_SRGB, xyz)
+}
+
+pub(crate) fn linear_to_lab(rgb: [f32; 3]) -> [f32; 3] {
+    let xyz = mul3(&D65_TO_D50, linear_to_xyz(rgb));
+    let f = |i: usize| {
+        let v = xyz[i] / D50_WHITE[i];
+        if v > LAB_EPSILON {
+            v.cbrt()
+        } else {
+            (LAB_KAPPA * v + 16.0) / 116.0
+        }
+    };
+    let (f0, f1, f2) = (f(0), f(1), f(2));
+
+    [116.0 * f1 - 16.0, 500.0 * (f0 - f1), 200.0 * (f1 - f2)]
+}
+
+pub(crate) fn lab_to_linear(lab: [f32; 3]) -> [f32; 3] {
+    let [l, a, b] = lab;
+    let f1 = (l + 16.0) / 116.0;
+    let f0 = a / 500.0 + f1;
+    let f2 = f1 - b / 200.0;
+
+    let x = if f0.powi(3) > LAB_EPSILON {
+        f0.powi(3)
+    } else {
+        (116.0 * f0 - 16.0) / LAB_KAPPA
+    };
+    let y = if l > LAB_KAPPA * LAB_EPSILON {
+        f1.powi(3)
+    } else {
+        l / LAB_KAPPA
+    };
+    let z = if f2.powi(3) > LAB_EPSILON {
+        f2.powi(3)
+    } else {
+        (116.0 * f2 - 16.0) / LAB_KAPPA
+    };
+
+    let xyz = [x * D50_WHITE[0], y * D50_WHITE[1], z * D50_WHITE[2]];
+    xyz_to_linear(mul3(&D50_TO_D65, xyz))
+}
+
+/// Convert rectangular (L, a, b) components to polar (L, C, h)
+pub(crate) fn to_polar(lab: [f32; 3]) -> [f32; 3] {
+    let c = (lab[1] * lab[1] + lab[2] * lab[2]).sqrt();
+    let h = lab[2].atan2(lab[1]).to_degrees().rem_euclid(360.0);
+    [lab[0], c, h]
+}
+
+/// Convert polar (L, C, h) components to rectangular (L, a, b)
+pub(crate) fn from_polar(lch: [f32; 3]) -> [f32; 3] {
+    let h = lch[2].to_radians();
+    [lch[0], lch[1] * h.cos(), lch[1] * h.sin()]
+}
+
+fn in_gamut(rgb: [f32; 3]) -> bool {
+    rgb.iter()
+        .all(|c| (-GAMUT_EPSILON..=1.0 + GAMUT_EPSILON).contains(c))
+}
+
+fn clip(rgb: [f32; 3]) -> [f32; 3] {
+    rgb.map(|c| c.clamp(0.0, 1.0))
+}
+
+fn delta_e_ok(a: [f32; 3], b: [f32; 3]) -> f32 {
+    let a = linear_to_oklab(a);
+    let b = linear_to_oklab(b);
+    ((a[0] - b[0]).powi(2) + (a[1] - b[1]).powi(2) + (a[2] - b[2]).powi(2)).sqrt()
+}
+
+/// Map linear sRGB into gamut by reducing Oklch chroma (CSS Color 4 algorithm)
+pub(crate) fn gamut_map(rgb: [f32; 3]) -> [f32; 3] {
+    if in_gamut(rgb) {
+        return clip(rgb);
+    }
+
+    let [l, c, h] = to_polar(linear_to_oklab(rgb));
+    if l >= 1.0 {
+        return [1.0, 1.0, 1.0];
+    }
+    if l <= 0.0 {
+        return [0.0, 0.0, 0.0];
+    }
+
+    let mut clipped = clip(rgb);
+    if delta_e_ok(clipped, rgb) < GAMUT_JND {
+        return clipped;
+    }
+
+    let (mut min, mut max) = (0.0, c);
+    let mut min_in_gamut = true;
+    while max - min > GAMUT_EPSILON {
+        let chroma = (min + max) / 2.0;
+        let current = oklab_to_linear(from_polar([l, chroma, h]));
+
+        if min_in_gamut && in_gamut(current) {
+            min = chroma;
+            continue;
+        }
+
+        clipped = clip(current);
+        let e = delta_e_ok(clipped, current);
+        if e < GAMUT_JND {
+            if GAMUT_JND - e < GAMUT_EPSILON {
+                break;
+            }
+            min_in_gamut = false;
+            min = chroma;
+        } else {
+            max = chroma;
+        }
+    }
+
+    clipped
+}
+
+fn srgb_to_hsl(rgb: [f32; 3]) -> [f32; 3] {
+    let [r, g, b] = rgb;
+    let max = r.max(g.max(b));
+    let min = r.min(g.min(b));
+    let delta = max - min;
+    let l = (max + min) / 2.0;
+
+    if delta == 0.0 {
+        return [0.0, 0.0, l * 100.0];
+    }
+
+    let s = if l < 0.5 {
+        delta / (max + min)
+    } else {
+        delta / (2.0 - max - min)
+    };
+    let h = match max {
+        x if x == r => ((g - b) / delta + if g < b { 6.0 } else { 0.0 }) * 60.0,
+        x if x == g => ((b - r) / delta + 2.0) * 60.0,
+        _ => ((r - g) / delta + 4.0) * 60.0,
+    };
+
+    [h, s * 100.0, l * 100.0]
+}
+
+pub(crate) fn hwb_to_srgb(hwb: [f32; 3]) -> [f32; 3] {
+    let w = hwb[1] / 100.0;
+    let b = hwb[2] / 100.0;
+    if w + b >= 1.0 {
+        let gray = w / (w + b);
+        return [gray; 3];
+    }
+
+    hsl_to_srgb([hwb[0], 100.0, 50.0]).map(|c| c * (1.0 - w - b) + w)
+}
+
+fn hsl_to_srgb(hsl: [f32; 3]) -> [f32; 3] {
+    let h = hsl[0].rem_euclid(360.0);
+    let s = hsl[1] / 100.0;
+    let l = hsl[2] / 100.0;
+    let f = |n: f32| {
+        let k = (n + h / 30.0) % 12.0;
+        l - s * l.min(1.0 - l) * (k - 3.0).min(9.0 - k).clamp(-1.0, 1.0)
+    };
+    [f(0.0), f(8.0), f(4.0)]
+}
+
+impl ColorSpace {
+    /// Index of the hue component for polar spaces
+    pub(crate) fn hue_index(self) -> Option<usize> {
+        match self {
+            ColorSpace::Lch | ColorSpace::Oklch => Some(2),
+            ColorSpace::Hsl | ColorSpace::Hwb => Some(0),
+            _ => None,
+        }
+    }
+
+    /// Components of a color in this space; a powerless hue is returned as NaN
+    pub(crate) fn components(self, color: &Color) -> [f32; 3] {
+        let linear = color.to_linear_rgb();
+        let mut components = match self {
+            ColorSpace::Srgb => [color.r, color.g, color.b].map(|c| c as f32 / 255.0),
+            ColorSpace::SrgbLinear => linear,
+            ColorSpace::Xyz => linear_to_xyz(linear),
+            ColorSpace::Lab => linear_to_lab(linear),
+            ColorSpace::Lch => to_polar(linear_to_lab(linear)),
+            ColorSpace::Oklab => linear_to_oklab(linear),
+            ColorSpace::Oklch => to_polar(linear_to_oklab(linear)),
+            ColorSpace::Hsl => srgb_to_hsl([color.r, color.g, color.b].map(|c| c as f32 / 255.0)),
+            ColorSpace::Hwb => {
+                let hwb = color.to_hwb();
+                [hwb.h, hwb.w, hwb.b]
+            }
+        };
+
+        let powerless = match self {
+            ColorSpace::Lch => components[1] < 0.05,
+            ColorSpace::Oklch => components[1] < 0.000_5,
+            ColorSpace::Hsl => components[1] < 0.01,
+            ColorSpace::Hwb => components[1] + components[2] >= 99.99,
+            _ => false,
+        };
+        if let (true, Some(i)) = (powerless, self.hue_index()) {
+            components[i] = f32::NAN;
+        }
+
+        components
+    }
+
+    /// Build a color from components in this space, treating a NaN hue as 0
+    pub(crate) fn compose(self, components: [f32; 3], alpha: f32) -> Color {
+        let mut c = components;
+        if let Some(i) = self.hue_index()
+            && c[i].is_nan()
+        {
+            c[i] = 0.0;
+        }
+
+        let linear = match self {
+            ColorSpace::Srgb => c.map(srgb_to_linear),
+            ColorSpace::SrgbLinear => c,
+            ColorSpace::Xyz => xyz_to_linear(c),
+            ColorSpace::Lab => lab_to_linear(c),
+            ColorSpace::Lch => lab_to_linear(from_polar(c)),
+            ColorSpace::Oklab => oklab_to_linear(c),
+            ColorSpace::Oklch => oklab_to_linear(from_polar(c)),
+            ColorSpace::Hsl => hsl_to_srgb(c).map(srgb_to_linear),
+            ColorSpace::Hwb => hwb_to_srgb(c).map(srgb_to_linear),
+        };
+        Color::from_linear_rgb(linear, alpha)
+    }
+}
+
+impl Color {
+    /// Linear-light sRGB channels (0.0 to 1.0)
+    pub(crate) fn to_linear_rgb(&self) -> [f32; 3] {
+        [self.r, self.g, self.b].map(|c| srgb_to_linear(c as f32 / 255.0))
+    }
+
+    /// Build a Color from linear-light sRGB channels, gamut mapping if needed
+    pub(crate) fn from_linear_rgb(rgb: [f32; 3], alpha: f32) -> Color {
+        let [r, g, b] = gamut_map(rgb).map(|c| (linear_to_srgb(c) * 255.0).round() as u8);
+        Color::from_rgba(r, g, b, alpha)
+    }
+
+    /// Euclidean distance in Oklab, ignoring alpha
+    pub(crate) fn oklab_distance(&self, other: &Color) -> f32 {
+        delta_e_ok(self.to_linear_rgb(), other.to_linear_rgb())
+    }
+
+    /// Create a Color from CIE XYZ values (D65)
+    pub fn from_xyz(x: f32, y: f32, z: f32) -> Color {
+        Color::from_linear_rgb(xyz_to_linear([x, y, z]), 1.0)
+    }
+
+    /// Create a Color from CIE Lab values (D50)
+    ///
+    /// Out-of-gamut values are mapped into sRGB by reducing chroma.
+    pub fn from_lab(l: f32, a: f32, b: f32) -> Color {
+        Color::from_linear_rgb(lab_to_linear([l, a, b]), 1.0)
+    }
+
+    /// Create a Color from CIE LCH values
+    pub fn from_lch(l: f32, c: f32, h: f32) -> Color {
+        Color::from_linear_rgb(lab_to_linear(from_polar([l, c, h])), 1.0)
+    }
+
+    /// Create a Color from Oklab values
+    pub fn from_oklab(l: f32, a: f32, b: f32) -> Color {
+        Color::from_linear_rgb(oklab_to_linear([l, a, b]), 1.0)
+    }
+
+    /// Create a Color from Oklch values
+    pub fn from_oklch(l: f32, c: f32, h: f32) -> Color {
+        Color::from_linear_rgb(oklab_to_linear(from_polar([l, c, h])), 1.0)
+    }
+
+    /// Convert to CIE XYZ (D65)
+    pub fn to_xyz(&self) -> Xyz {
+        let [x, y, z] = linear_to_xyz(self.to_linear_rgb());
+        Xyz { x, y, z }
+    }
+
+    /// Convert to CIE Lab (D50)
+    pub fn to_lab(&self) -> Lab {
+        let [l, a, b] = linear_to_lab(self.to_linear_rgb());
+        Lab { l, a, b }
+    }
+
+    /// Convert to CIE LCH
+    pub fn to_lch(&self) -> Lch {
+        let [l, c, h] = to_polar(linear_to_lab(self.to_linear_rgb()));
+        Lch { l, c, h }
+    }
+
+    /// Convert to Oklab
+    pub fn to_oklab(&self) -> Oklab {
+        let [l, a, b] = linear_to_oklab(self.to_linear_rgb());
+        Oklab { l, a, b }
+    }
+
+    /// Convert to Oklch
+    pub fn to_oklch(&self) -> Oklch {
+        let [l, c, h] = to_polar(linear_to_oklab(self.to_linear_rgb()));
+        Oklch { l, c, h }
+    }
+
+    /// Darken the color by a percentage of its lightness in the given space
+    pub fn darken_in(&self, percentage: f32, mode: LightnessMode) -> Color {
+        let factor = 1.0 - (percentage / 100.0).clamp(0.0, 1.0);
+        self.map_lightness(mode, |l, _| l * factor)
+            .unwrap_or_else(|| self.darken(percentage))
+    }
+
+    /// Lighten the color by a percentage of its remaining lightness in the given space
+    pub fn lighten_in(&self, percentage: f32, mode: LightnessMode) -> Color {
+        let factor = (percentage / 100.0).clamp(0.0, 1.0);
+        self.map_lightness(mode, |l, max| l + (max - l) * factor)
+            .unwrap_or_else(|| self.lighten(percentage))
+    }
+
+    /// Apply `f(lightness, max_lightness)` in a perceptual space; `None` for sRGB
+    fn map_lightness(&self, mode: LightnessMode, f: impl Fn(f32, f32) -> f32) -> Option<Color> {
+        let rgb = match mode {
+            LightnessMode::Srgb => return None,
+            LightnessMode::Lab => {
+                let [l, a, b] = linear_to_lab(self.to_linear_rgb());
+                lab_to_linear([f(l, 100.0), a, b])
+            }
+            LightnessMode::Oklch => {
+                let [l, a, b] = linear_to_oklab(self.to_linear_rgb());
+                oklab_to_linear([f(l, 1.0), a, b])
+            }
+        };
+        Some(Color::from_linear_rgb(rgb, self.a))
+    }
+}
+
+impl fmt::Display for Xyz {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "xyz({:.4}, {:.4}, {:.4})", self.x, self.y, self.z)
+    }
+}
+
+impl fmt::Display for Lab {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "lab({:.1}%, {:.1}, {:.1})", self.l, self.a, self.b)
+    }
+}
+
+impl fmt::Display for Lch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "lch({:.1}%, {:.1}, {:.1}°)", self.l, self.c, self.h)
+    }
+}
+
+impl fmt::Display for Oklab {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "oklab({:.3}, {:.3}, {:.3})", self.l, self.a, self.b)
+    }
+}
+
+impl fmt::Display for Oklch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "oklch({:.3}, {:.3}, {:.1}°)", self.l, self.c, self.h)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lab_conversion() {
+        let white = Color::WHITE.to_lab();
+        assert!((white.l - 100.0).abs() < 0.01);
+        assert!(white.a.abs() < 0.01);
+        assert!(white.b.abs() < 0.01);
+
+        let red = Color::RED.to_lab();
+        assert!((red.l - 54.29).abs() < 0.1);
+        assert!((red.a - 80.8).abs() < 0.2);
+        assert!((red.b - 69.89).abs() < 0.2);
+    }
+
+    #[test]
+    fn test_oklch_conversion() {
+        let red = Color::RED.to_oklch();
+        assert!((red.l - 0.628).abs() < 0.001);
+        assert!((red.c - 0.2577).abs() < 0.001);
+        assert!((red.h - 29.23).abs() < 0.1);
+    }
+
+    #[test]
+    fn test_perceptual_round_trip() {
+        let color = Color::from_hex("#4080FF").unwrap();
+        let lab = color.to_lab();
+        let lch = color.to_lch();
+        let oklch = color.to_oklch();
+
+        assert_eq!(Color::from_lab(lab.l, lab.a, lab.b), color);
+        assert_eq!(Color::from_lch(lch.l, lch.c, lch.h), color);
+        assert_eq!(Color::from_oklch(oklch.l, oklch.c, oklch.h), color);
+    }
+
+    #[test]
+    fn test_out_of_gamut_is_mapped() {
+        let color = Color::from_oklch(0.7, 0.4, 150.0);
+        let mapped = color.to_oklch();
+        assert!((mapped.l - 0.7).abs() < 0.03);
+        assert!((mapped.h - 150.0).abs() < 3.0);
+    }
+
+    #[test]
+    fn test_perceptual_lighten_darken() {
+        let color = Color::from_hex("#4080FF").unwrap();
+        assert_eq!(
+            color.lighten_in(30.0, LightnessMode::Srgb),
+            color.lighten(30.0)
+        );
+        assert_eq!(
+            color.darken_in(30.0, LightnessMode::Srgb),
+            color.darken(30.0)
+        );
+
+        let before = color.to_oklch();
+        let lighter = color.lighten_in(20.0, LightnessMode::Oklch).to_oklch();
+        let darker = color.darken_in(20.0, LightnessMode::Oklch).to_oklch();
+        assert!((lighter.l - (before.l + (1.0 - before.l) * 0.2)).abs() < 0.01);
+        assert!((darker.l - before.l * 0.8).abs() < 0.01);
+        assert!((darker.h - before.h).abs() < 2.0);
+
+        let muted = Color::from_hex("#807060").unwrap();
+        let lab_darker = muted.darken_in(30.0, LightnessMode::Lab).to_lab();
+        assert!((lab_darker.l - muted.to_lab().l * 0.7).abs() < 0.5);
+    }
+}
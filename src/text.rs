@@ -0,0 +1,91 @@
+//! Styled text spans shared by the text format parsers and renderers
+
+use crate::Color;
+
+/// Color and decorations applied to a run of text
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Style {
+    pub color: Option<Color>, // `None` for the renderer's default color
+    /// Background color, set by [`Text::from_ansi`]
+    ///
+    /// Only [`Text::to_ansi`] and [`Text::to_html`] show it. Minecraft has no
+    /// text backgrounds, so legacy, JSON, MiniMessage and MOTD output drop it.
+    pub background: Option<Color>,
+    pub bold: bool,
+    pub italic: bool,
+    pub underlined: bool,
+    pub strikethrough: bool,
+    pub obfuscated: bool,
+}
+
+/// A run of text with a single style
+#[derive(Debug, Clone, PartialEq)]
+pub struct Span {
+    pub text: String,
+    pub style: Style,
+}
+
+/// Formatted text as a sequence of styled spans
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Text {
+    pub spans: Vec<Span>,
+}
+
+impl Style {
+    /// A style with only a color set
+    pub fn colored(color: &Color) -> Self {
+        Style {
+            color: Some(color.clone()),
+            ..Style::default()
+        }
+    }
+
+    /// Whether any decoration is enabled
+    pub fn is_decorated(&self) -> bool {
+        self.bold || self.italic || self.underlined || self.strikethrough || self.obfuscated
+    }
+}
+
+impl Text {
+    /// Create empty text
+    pub fn new() -> Self {
+        Text::default()
+    }
+
+    /// Append text, merging it into the last span when the style matches
+    pub fn push(&mut self, text: &str, style: &Style) {
+        if text.is_empty() {
+            return;
+        }
+        match self.spans.last_mut() {
+            Some(last) if last.style == *style => last.text.push_str(text),
+            _ => self.spans.push(Span {
+                text: text.to_string(),
+                style: style.clone(),
+            }),
+        }
+    }
+
+    /// The text without any styling
+    pub fn plain(&self) -> String {
+        self.spans.iter().map(|span| span.text.as_str()).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_push_merges_spans() {
+        let mut text = Text::new();
+        text.push("Hello ", &Style::colored(&Color::RED));
+        text.push("", &Style::default());
+        text.push("world", &Style::colored(&Color::RED));
+        text.push("!", &Style::default());
+
+        assert_eq!(text.spans.len(), 2);
+        assert_eq!(text.spans[0].text, "Hello world");
+        assert_eq!(text.plain(), "Hello world!");
+    }
+}